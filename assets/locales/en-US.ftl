@@ -0,0 +1,103 @@
+## Game window and HUD
+
+window-title = Mine Sweeper!
+player-name = Player { $number }
+player-default = Player
+player-someone = Someone
+
+hud-online = Online on { $address }
+hud-spectator = Spectating, M shows the mines
+hud-race = Race! { $players }
+hud-race-versus = vs
+hud-daily = Daily challenge { $date } { $difficulty }
+hud-daily-official = { $challenge } - official attempt
+hud-daily-practice = { $challenge } - practice
+
+difficulty-beginner = Beginner
+difficulty-intermediate = Intermediate
+difficulty-expert = Expert
+
+race-finished-first = { $player } finished first!
+race-finished = { $player } finished
+race-exploded = { $player } exploded!
+
+daily-cleared = Cleared in { $time }s!
+daily-exploded = Exploded!
+
+versus-status = { $player } { $cleared }% { $flags ->
+    [one] 1 flag
+   *[other] { $flags } flags
+} { $state }
+versus-alive = alive
+versus-cleared = cleared
+versus-dead = dead
+versus-ranking = Ranking
+versus-rank = { $rank }. { $player } { $result }
+versus-time = { $time }s { $speed } 3BV/s
+versus-dead-at = dead at { $cleared }%
+
+## Boards, as read by assistive technologies
+
+board-label = Board
+board-label-player = { $player } board
+board-grid = { $board }, { $width } by { $height }, { $mines ->
+    [one] 1 mine
+   *[other] { $mines } mines
+}, { $flags ->
+    [one] 1 flag
+   *[other] { $flags } flags
+}
+tile-label = { $coordinates }: { $state }
+tile-covered = covered
+tile-flagged = { $count ->
+    [one] flagged
+   *[other] { $count } flags
+}
+tile-questioned = question mark
+tile-mine = mine
+tile-exploded = { $count ->
+    [one] exploded mine
+   *[other] { $count } exploded mines
+}
+tile-empty = empty
+neighborhood = { $tile }. Around: { $neighbors }
+neighbor = { $direction } { $state }
+direction-north = north
+direction-south = south
+direction-east = east
+direction-west = west
+direction-here = here
+direction-distance = { $distance } { $direction }
+announce-explosion = Boom! A mine exploded
+announce-completed = Board completed!
+
+## Settings screen
+
+settings-title = Settings
+settings-help = Up and Down select, Left and Right change, Return binds a key, O closes
+settings-rebinding = Press the new key, Escape cancels
+settings-key-reserved = { $key } is a game shortcut, press another key or Escape
+settings-key-taken = { $key } is already bound to { $action }, press another key or Escape
+settings-row = { $label }: { $value }
+settings-on = on
+settings-off = off
+settings-theme = Theme
+settings-tile-size = Tile size
+settings-tile-adaptive = fit to the window
+settings-tile-fixed = { $size } px
+settings-padding = Tile padding
+settings-padding-theme = from the theme
+settings-safe-start = Safe start
+settings-question-marks = Question marks
+settings-sounds = Sounds
+settings-volume = Volume
+settings-animations = Animations
+settings-reveal-animation = Reveal waves
+settings-key-up = Cursor up
+settings-key-down = Cursor down
+settings-key-left = Cursor left
+settings-key-right = Cursor right
+settings-key-reveal = Reveal
+settings-key-mark = Mark
+settings-key-chord = Chord
+settings-key-describe = Describe
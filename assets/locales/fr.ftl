@@ -0,0 +1,104 @@
+## Fenêtre et affichage du jeu
+# La police du jeu n'a pas de lettres accentuées autres que é, è, ç, ë et ï
+
+window-title = Démineur !
+player-name = Joueur { $number }
+player-default = Joueur
+player-someone = Quelqu'un
+
+hud-online = En ligne sur { $address }
+hud-spectator = Spectateur, M affiche les mines
+hud-race = Course ! { $players }
+hud-race-versus = contre
+hud-daily = Défi du jour { $date } { $difficulty }
+hud-daily-official = { $challenge } - essai officiel
+hud-daily-practice = { $challenge } - essai libre
+
+difficulty-beginner = Débutant
+difficulty-intermediate = Intermédiaire
+difficulty-expert = Expert
+
+race-finished-first = { $player } a fini premier !
+race-finished = { $player } a fini
+race-exploded = { $player } a explosé !
+
+daily-cleared = Terminé en { $time } s !
+daily-exploded = Explosé !
+
+versus-status = { $player } { $cleared } % { $flags ->
+    [one] { $flags } drapeau
+   *[other] { $flags } drapeaux
+} { $state }
+versus-alive = en vie
+versus-cleared = terminé
+versus-dead = mort
+versus-ranking = Classement
+versus-rank = { $rank }. { $player } { $result }
+versus-time = { $time } s { $speed } 3BV/s
+versus-dead-at = mort, { $cleared } % déminé
+
+## Plateaux, lus par les technologies d'assistance
+
+board-label = Plateau
+board-label-player = Plateau de { $player }
+board-grid = { $board }, { $width } sur { $height }, { $mines ->
+    [one] { $mines } mine
+   *[other] { $mines } mines
+}, { $flags ->
+    [one] { $flags } drapeau
+   *[other] { $flags } drapeaux
+}
+tile-label = { $coordinates } : { $state }
+tile-covered = couvert
+tile-flagged = { $count ->
+    [one] marqué
+   *[other] { $count } drapeaux
+}
+tile-questioned = point d'interrogation
+tile-mine = mine
+tile-exploded = { $count ->
+    [one] mine explosée
+   *[other] { $count } mines explosées
+}
+tile-empty = vide
+neighborhood = { $tile }. Autour : { $neighbors }
+neighbor = { $direction } { $state }
+direction-north = nord
+direction-south = sud
+direction-east = est
+direction-west = ouest
+direction-here = ici
+direction-distance = { $distance } { $direction }
+announce-explosion = Boum ! Une mine a explosé
+announce-completed = Plateau terminé !
+
+## Écran des paramètres
+
+settings-title = Paramètres
+settings-help = Haut et Bas choisissent, Gauche et Droite changent, Entrée associe une touche, O ferme
+settings-rebinding = Appuyez sur la nouvelle touche, Echap annule
+settings-key-reserved = { $key } est un raccourci du jeu, appuyez sur une autre touche ou Echap
+settings-key-taken = { $key } est prise par { $action }, appuyez sur une autre touche ou Echap
+settings-row = { $label } : { $value }
+settings-on = oui
+settings-off = non
+settings-theme = Thème
+settings-tile-size = Taille des cases
+settings-tile-adaptive = automatique
+settings-tile-fixed = { $size } px
+settings-padding = Marge des cases
+settings-padding-theme = celle du thème
+settings-safe-start = Premier clic sans mine
+settings-question-marks = Points d'interrogation
+settings-sounds = Sons
+settings-volume = Volume
+settings-animations = Animations
+settings-reveal-animation = Vagues de révélation
+settings-key-up = Curseur vers le haut
+settings-key-down = Curseur vers le bas
+settings-key-left = Curseur vers la gauche
+settings-key-right = Curseur vers la droite
+settings-key-reveal = Révéler
+settings-key-mark = Marquer
+settings-key-chord = Accord
+settings-key-describe = Décrire
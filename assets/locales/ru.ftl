@@ -0,0 +1,108 @@
+## Окно и интерфейс игры
+
+window-title = Сапёр!
+player-name = Игрок { $number }
+player-default = Игрок
+player-someone = Кто-то
+
+hud-online = В сети на { $address }
+hud-spectator = Просмотр, M показывает мины
+hud-race = Гонка! { $players }
+hud-race-versus = против
+hud-daily = Задача дня { $date } { $difficulty }
+hud-daily-official = { $challenge } - зачётная попытка
+hud-daily-practice = { $challenge } - тренировка
+
+difficulty-beginner = Новичок
+difficulty-intermediate = Любитель
+difficulty-expert = Профессионал
+
+race-finished-first = { $player } финишировал первым!
+race-finished = { $player } финишировал
+race-exploded = { $player } подорвался!
+
+daily-cleared = Пройдено за { $time } с!
+daily-exploded = Взрыв!
+
+versus-status = { $player } { $cleared }% { $flags ->
+    [one] { $flags } флаг
+    [few] { $flags } флага
+   *[other] { $flags } флагов
+} { $state }
+versus-alive = в игре
+versus-cleared = прошёл
+versus-dead = подорвался
+versus-ranking = Рейтинг
+versus-rank = { $rank }. { $player } { $result }
+versus-time = { $time } с { $speed } 3BV/с
+versus-dead-at = подорвался на { $cleared }%
+
+## Поля для вспомогательных технологий
+
+board-label = Поле
+board-label-player = Поле игрока { $player }
+board-grid = { $board }, { $width } на { $height }, { $mines ->
+    [one] { $mines } мина
+    [few] { $mines } мины
+   *[other] { $mines } мин
+}, { $flags ->
+    [one] { $flags } флаг
+    [few] { $flags } флага
+   *[other] { $flags } флагов
+}
+tile-label = { $coordinates }: { $state }
+tile-covered = закрыта
+tile-flagged = { $count ->
+    [one] с флагом
+    [few] { $count } флага
+   *[other] { $count } флагов
+}
+tile-questioned = под вопросом
+tile-mine = мина
+tile-exploded = { $count ->
+    [one] взорванная мина
+    [few] { $count } взорванные мины
+   *[other] { $count } взорванных мин
+}
+tile-empty = пусто
+neighborhood = { $tile }. Вокруг: { $neighbors }
+neighbor = { $direction } { $state }
+direction-north = север
+direction-south = юг
+direction-east = восток
+direction-west = запад
+direction-here = здесь
+direction-distance = { $distance } { $direction }
+announce-explosion = Бум! Мина взорвалась
+announce-completed = Поле пройдено!
+
+## Экран настроек
+
+settings-title = Настройки
+settings-help = Вверх и вниз выбирают, влево и вправо меняют, Enter назначает клавишу, O закрывает
+settings-rebinding = Нажмите новую клавишу, Escape отменяет
+settings-key-reserved = { $key } используется игрой, нажмите другую клавишу или Escape
+settings-key-taken = { $key } уже назначена: { $action }, нажмите другую клавишу или Escape
+settings-row = { $label }: { $value }
+settings-on = вкл
+settings-off = выкл
+settings-theme = Тема
+settings-tile-size = Размер клеток
+settings-tile-adaptive = по размеру окна
+settings-tile-fixed = { $size } пикс.
+settings-padding = Отступ клеток
+settings-padding-theme = из темы
+settings-safe-start = Безопасный старт
+settings-question-marks = Вопросительные знаки
+settings-sounds = Звуки
+settings-volume = Громкость
+settings-animations = Анимации
+settings-reveal-animation = Волны открытия
+settings-key-up = Курсор вверх
+settings-key-down = Курсор вниз
+settings-key-left = Курсор влево
+settings-key-right = Курсор вправо
+settings-key-reveal = Открыть
+settings-key-mark = Пометить
+settings-key-chord = Аккорд
+settings-key-describe = Описать
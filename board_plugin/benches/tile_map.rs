@@ -0,0 +1,63 @@
+use board_plugin::components::Coordinates;
+use board_plugin::resources::{Tile, TileMap};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+/// Benchmarked board sizes: expert board and a huge board
+const BOARDS: [(&str, u16, u16, u16); 2] =
+    [("30x16", 30, 16, 99), ("1000x1000", 1000, 1000, 60_000)];
+
+fn generated(width: u16, height: u16, bomb_count: u16) -> TileMap {
+    let mut tile_map = TileMap::empty(width, height);
+    tile_map.set_bombs(bomb_count);
+    tile_map
+}
+
+fn generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generation");
+    for (label, width, height, bomb_count) in BOARDS {
+        group.bench_function(label, |b| {
+            b.iter(|| generated(black_box(width), black_box(height), black_box(bomb_count)))
+        });
+    }
+    group.finish();
+}
+
+fn flood_fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flood_fill");
+    for (label, width, height, bomb_count) in BOARDS {
+        let tile_map = generated(width, height, bomb_count);
+        let start = tile_map
+            .iter()
+            .find(|(_, tile)| *tile == Tile::Empty)
+            .map(|(coords, _)| coords)
+            .unwrap_or_default();
+        group.bench_function(label, |b| {
+            b.iter_batched(
+                || tile_map.clone(),
+                |mut tile_map| tile_map.reveal_from(black_box(start)),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn flag_toggling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flag_toggling");
+    for (label, width, height, bomb_count) in BOARDS {
+        let mut tile_map = generated(width, height, bomb_count);
+        group.bench_function(label, |b| {
+            b.iter(|| {
+                for y in 0..height {
+                    for x in 0..width {
+                        black_box(tile_map.cycle_flag(Coordinates { x, y }));
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, generation, flood_fill, flag_toggling);
+criterion_main!(benches);
@@ -0,0 +1,22 @@
+//! Plays expert boards with the simple solver and prints a JSON report.
+//!
+//! `cargo run --release -p board_plugin --example simulate -- [games] [seed]`
+use board_plugin::resources::BoardOptions;
+use board_plugin::simulation::{SimpleSolver, Simulation};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let games = args.next().and_then(|a| a.parse().ok()).unwrap_or(1000);
+    let seed = args.next().and_then(|a| a.parse().ok()).unwrap_or(0);
+    let options = BoardOptions {
+        map_size: (30, 16),
+        bomb_count: 99,
+        safe_start: true,
+        seed: Some(seed),
+        ..Default::default()
+    };
+    let report = Simulation::new(options, games)
+        .run(&mut SimpleSolver::default())
+        .expect("Expert boards are valid");
+    println!("{}", report.to_json());
+}
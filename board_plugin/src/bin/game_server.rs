@@ -0,0 +1,64 @@
+//! Multiplayer game server, every connected player playing the same board together in co-op
+//! mode, or its own copy of it in versus mode.
+//!
+//! `cargo run -p board_plugin --bin game_server -- [coop|versus] [address] [width] [height] [mines]`
+use std::net::TcpListener;
+
+use bevy::log::{self, LogPlugin};
+use bevy::prelude::App;
+use board_plugin::net::{GameServer, NetMode, DEFAULT_ADDRESS};
+use board_plugin::resources::BoardOptions;
+
+fn main() {
+    // Only installs the logger, the server runs its own loop
+    App::new().add_plugin(LogPlugin::default());
+
+    let mut args = std::env::args().skip(1);
+    let mode = match args.next().as_deref() {
+        None | Some("coop") => NetMode::Coop,
+        Some("versus") => NetMode::Versus,
+        Some(mode) => {
+            log::error!("Unknown mode `{}`, expected `coop` or `versus`", mode);
+            std::process::exit(1);
+        }
+    };
+    let address = args.next().unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+    let mut number = |default| args.next().and_then(|a| a.parse().ok()).unwrap_or(default);
+    // Versus players race on smaller boards
+    let options = match mode {
+        NetMode::Coop => BoardOptions {
+            map_size: (number(40), number(30)),
+            bomb_count: number(200),
+            safe_start: true,
+            ..Default::default()
+        },
+        NetMode::Versus => BoardOptions {
+            map_size: (number(16), number(16)),
+            bomb_count: number(40),
+            safe_start: true,
+            ..Default::default()
+        },
+    };
+    let listener = match TcpListener::bind(&address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            log::error!("Failed to listen on {}, {}", address, e);
+            std::process::exit(1);
+        }
+    };
+    log::info!(
+        "Serving {:?} games on {}x{} boards with {} mines on {}",
+        mode,
+        options.map_size.0,
+        options.map_size.1,
+        options.bomb_count,
+        address
+    );
+    match GameServer::new(options) {
+        Ok(server) => server.with_mode(mode).run(listener),
+        Err(e) => {
+            log::error!("Invalid board options, {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::Component;
+
+/// Live region of a board, announcing its explosion, its completion and the requested
+/// neighborhood descriptions to assistive technologies
+#[derive(Debug, Default, Clone, Component)]
+pub struct BoardAnnouncer {
+    /// Last announcement
+    pub text: String,
+}
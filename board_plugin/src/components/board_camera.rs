@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// Split screen camera following a board, rendering to the `index`-th vertical slice of the
+/// window
+#[derive(Debug, Copy, Clone, Component)]
+pub struct BoardCamera {
+    pub board: Entity,
+    pub index: usize,
+}
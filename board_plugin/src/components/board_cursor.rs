@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use crate::components::Coordinates;
+
+/// Tile cursor of a keyboard or gamepad controlled board, spawned as a child of the board
+#[derive(Debug, Copy, Clone, Component)]
+pub struct BoardCursor(pub Coordinates);
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Player owning a board, inserted on its board entity
+#[derive(Debug, Clone, Component, Serialize, Deserialize)]
+pub struct BoardPlayer {
+    pub name: String,
+    pub input: BoardInput,
+}
+
+/// Input bindings controlling a board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BoardInput {
+    /// Mouse buttons on the hovered tile: left reveals, right marks and middle chords
+    Mouse,
+    /// Keyboard moved tile cursor
+    Keyboard(KeyBindings),
+    /// Gamepad moved tile cursor
+    Gamepad(GamepadBindings),
+}
+
+/// Keyboard bindings of a board cursor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub reveal: KeyCode,
+    pub mark: KeyCode,
+    pub chord: KeyCode,
+    /// Describes the tiles around the cursor, see [`crate::events::DescribeNeighborhoodEvent`]
+    pub describe: KeyCode,
+}
+
+/// Gamepad bindings of a board cursor, the cursor being moved with the D-pad
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GamepadBindings {
+    pub gamepad: Gamepad,
+    pub reveal: GamepadButtonType,
+    pub mark: GamepadButtonType,
+    pub chord: GamepadButtonType,
+    pub describe: GamepadButtonType,
+}
+
+impl Default for BoardPlayer {
+    fn default() -> Self {
+        Self {
+            name: "Player".to_string(),
+            input: BoardInput::Mouse,
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Arrow keys, `Return` reveals, right `Shift` marks, right `Ctrl` chords and `/` describes
+    pub fn arrows() -> Self {
+        Self {
+            up: KeyCode::Up,
+            down: KeyCode::Down,
+            left: KeyCode::Left,
+            right: KeyCode::Right,
+            reveal: KeyCode::Return,
+            mark: KeyCode::RShift,
+            chord: KeyCode::RControl,
+            describe: KeyCode::Slash,
+        }
+    }
+}
+
+impl GamepadBindings {
+    /// South button reveals, east button marks, west button chords and north button describes
+    pub fn new(gamepad: Gamepad) -> Self {
+        Self {
+            gamepad,
+            reveal: GamepadButtonType::South,
+            mark: GamepadButtonType::East,
+            chord: GamepadButtonType::West,
+            describe: GamepadButtonType::North,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::{Component, Vec3};
+use bevy::time::Timer;
+
+/// Shakes a board after an explosion.
+///
+/// The board moves rather than the cameras, split screen boards shaking on their own
+#[derive(Debug, Clone, Component)]
+pub struct BoardShake {
+    pub timer: Timer,
+    /// Board translation restored once the shake is over
+    pub origin: Vec3,
+    /// Initial shake offset, fading over time
+    pub amplitude: f32,
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::Component;
-use bevy::reflect::Reflect;
 
+#[cfg(feature = "debug")]
+use bevy::reflect::Reflect;
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::prelude::ReflectInspectorOptions;
 #[cfg(feature = "debug")]
@@ -10,4 +11,7 @@ use bevy_inspector_egui::InspectorOptions;
 #[cfg_attr(feature = "debug", derive(Reflect, InspectorOptions))]
 #[cfg_attr(feature = "debug", reflect(InspectorOptions))]
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
-pub struct Bomb;
+pub struct Bomb {
+    /// Number of mines held by the tile
+    pub count: u8,
+}
@@ -0,0 +1,13 @@
+use bevy::prelude::{Component, Vec2};
+use bevy::time::Timer;
+
+/// Confetti thrown over a completed board, despawned once its timer is finished
+#[derive(Debug, Clone, Component)]
+pub struct Confetti {
+    pub timer: Timer,
+    pub velocity: Vec2,
+    /// Downward acceleration, scaled to the board
+    pub gravity: f32,
+    /// Rotation speed in radians per second
+    pub spin: f32,
+}
@@ -1,8 +1,10 @@
 use bevy::prelude::Component;
-use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::ops::{Add, Sub};
 
+#[cfg(feature = "debug")]
+use bevy::reflect::Reflect;
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::prelude::ReflectInspectorOptions;
 #[cfg(feature = "debug")]
@@ -10,7 +12,20 @@ use bevy_inspector_egui::InspectorOptions;
 
 #[cfg_attr(feature = "debug", derive(Reflect, InspectorOptions))]
 #[cfg_attr(feature = "debug", reflect(InspectorOptions))]
-#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    Ord,
+    PartialOrd,
+    Eq,
+    PartialEq,
+    Hash,
+    Component,
+    Serialize,
+    Deserialize,
+)]
 pub struct Coordinates {
     pub x: u16,
     pub y: u16,
@@ -1,9 +1,27 @@
+mod board_announcer;
+mod board_camera;
+mod board_cursor;
+mod board_player;
+mod board_shake;
 mod bomb;
 mod bomb_neighbor;
+mod confetti;
 mod coordinates;
+mod remote_board;
+mod spectated_board;
 mod uncover;
+mod uncover_delay;
 
+pub use board_announcer::BoardAnnouncer;
+pub use board_camera::BoardCamera;
+pub use board_cursor::BoardCursor;
+pub use board_player::*;
+pub use board_shake::BoardShake;
 pub use bomb::Bomb;
 pub use bomb_neighbor::BombNeighbor;
+pub use confetti::Confetti;
 pub use coordinates::Coordinates;
+pub use remote_board::{RemoteBoard, RemoteCursor};
+pub use spectated_board::SpectatedBoard;
 pub use uncover::Uncover;
+pub use uncover_delay::UncoverDelay;
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::net::{NetMode, PlayerId, PlayerProgress, RemotePlayer};
+
+/// Board replicating the board of a [`crate::net::GameServer`], its actions being played on
+/// the server
+#[derive(Debug, Clone, Component)]
+pub struct RemoteBoard {
+    /// Local player id
+    pub player: PlayerId,
+    pub mode: NetMode,
+    /// Other players of the board
+    pub players: HashMap<PlayerId, RemotePlayer>,
+    /// Progress of the versus players, the local player included
+    pub progress: HashMap<PlayerId, PlayerProgress>,
+    /// Final versus ranking, once every player finished
+    pub ranking: Option<Vec<PlayerProgress>>,
+}
+
+/// Cursor of another player on a [`RemoteBoard`], spawned as a child of the board
+#[derive(Debug, Copy, Clone, Component)]
+pub struct RemoteCursor {
+    pub player: PlayerId,
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+/// Read-only board replaying the actions streamed by a [`crate::net::SpectatorServer`]
+#[derive(Debug, Default, Copy, Clone, Component)]
+pub struct SpectatedBoard {
+    /// Shows the mines under the covers, only to the spectator
+    pub show_mines: bool,
+}
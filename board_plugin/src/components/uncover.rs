@@ -1,6 +1,7 @@
 use bevy::prelude::Component;
-use bevy::reflect::Reflect;
 
+#[cfg(feature = "debug")]
+use bevy::reflect::Reflect;
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::prelude::ReflectInspectorOptions;
 #[cfg(feature = "debug")]
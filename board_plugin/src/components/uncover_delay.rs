@@ -0,0 +1,7 @@
+use bevy::prelude::Component;
+use bevy::time::Timer;
+
+/// Delays the uncovering of a tile cover, the `Uncover` component is added once the timer is
+/// finished
+#[derive(Debug, Clone, Component)]
+pub struct UncoverDelay(pub Timer);
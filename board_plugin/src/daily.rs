@@ -0,0 +1,232 @@
+//! Daily challenges: one board per UTC day and difficulty, the same for every player.
+//!
+//! The first attempt of a challenge is the official one, recorded in the local
+//! [`DailyStats`], the later attempts being practice.
+//!
+//! ```no_run
+//! use board_plugin::daily::{DailyChallenge, DailyStats};
+//! use board_plugin::resources::Difficulty;
+//!
+//! let challenge = DailyChallenge::today(Difficulty::Expert);
+//! let mut stats = DailyStats::load("stats.json").unwrap();
+//! let attempt = stats.start_attempt(&challenge);
+//! ```
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{BoardOptions, BoardSource, Difficulty, Neighborhood, Topology};
+use crate::simulation::Outcome;
+
+/// UTC calendar date
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct Date {
+    pub year: i32,
+    /// Month from 1 to 12
+    pub month: u8,
+    /// Day of the month from 1
+    pub day: u8,
+}
+
+impl Date {
+    /// Current UTC date
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::from_days((seconds / 86_400) as i64)
+    }
+
+    /// Date `days` days after 1970-01-01
+    pub fn from_days(days: i64) -> Self {
+        // Howard Hinnant's civil from days algorithm, eras being 400 years long
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        Self {
+            year: (yoe + era * 400 + (month <= 2) as i64) as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// Days since 1970-01-01
+    pub fn days(&self) -> i64 {
+        let (month, day) = (self.month as i64, self.day as i64);
+        let year = self.year as i64 - (month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Board of a day and difficulty
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct DailyChallenge {
+    pub date: Date,
+    pub difficulty: Difficulty,
+}
+
+impl DailyChallenge {
+    /// Challenge of the current UTC day
+    pub fn today(difficulty: Difficulty) -> Self {
+        Self {
+            date: Date::today(),
+            difficulty,
+        }
+    }
+
+    /// Board seed, derived from the date and difficulty only
+    pub fn seed(&self) -> u64 {
+        let difficulty = Difficulty::ALL
+            .iter()
+            .position(|d| *d == self.difficulty)
+            .unwrap_or_default();
+        // SplitMix64 finalizer, spreading consecutive days over the whole seed range
+        let mut z = ((self.date.days() as u64) << 2 | difficulty as u64)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Options generating the challenge board, keeping the display options of `base`
+    pub fn board_options(&self, base: BoardOptions) -> BoardOptions {
+        BoardOptions {
+            source: BoardSource::Random,
+            seed: Some(self.seed()),
+            map_size: self.difficulty.map_size(),
+            bomb_count: self.difficulty.bomb_count(),
+            max_mines_per_tile: 1,
+            topology: Topology::Square,
+            neighborhood: Neighborhood::Moore,
+            wrapping: false,
+            safe_start: true,
+            ..base
+        }
+    }
+}
+
+/// Kind of a daily challenge attempt
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AttemptKind {
+    /// First attempt of the challenge
+    Official,
+    Practice,
+}
+
+/// Result of a daily challenge attempt
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttemptResult {
+    /// `Abandoned` until the attempt is finished
+    pub outcome: Outcome,
+    /// Duration in seconds
+    pub time: f32,
+    /// Board 3BV
+    pub three_bv: usize,
+}
+
+/// Attempts of a daily challenge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyRecord {
+    pub challenge: DailyChallenge,
+    pub official: AttemptResult,
+    /// Number of practice attempts
+    pub practice_attempts: u32,
+    /// Best time of the won practice attempts
+    pub best_practice_time: Option<f32>,
+}
+
+/// Local daily challenge records, stored as JSON
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyStats {
+    pub records: Vec<DailyRecord>,
+}
+
+impl DailyStats {
+    /// Loads the stats at `path`, empty if the file doesn't exist yet
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Saves the stats at `path`, creating its directory if needed
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn record(&self, challenge: &DailyChallenge) -> Option<&DailyRecord> {
+        self.records.iter().find(|r| r.challenge == *challenge)
+    }
+
+    /// Starts an attempt of `challenge`, the first one being official.
+    ///
+    /// The official attempt is recorded as abandoned until finished, leaving a game doesn't
+    /// give another official attempt.
+    pub fn start_attempt(&mut self, challenge: &DailyChallenge) -> AttemptKind {
+        match self.records.iter_mut().find(|r| r.challenge == *challenge) {
+            Some(record) => {
+                record.practice_attempts += 1;
+                AttemptKind::Practice
+            }
+            None => {
+                self.records.push(DailyRecord {
+                    challenge: *challenge,
+                    official: AttemptResult {
+                        outcome: Outcome::Abandoned,
+                        time: 0.,
+                        three_bv: 0,
+                    },
+                    practice_attempts: 0,
+                    best_practice_time: None,
+                });
+                AttemptKind::Official
+            }
+        }
+    }
+
+    /// Records the result of a started attempt
+    pub fn finish_attempt(
+        &mut self,
+        challenge: &DailyChallenge,
+        kind: AttemptKind,
+        result: AttemptResult,
+    ) {
+        let Some(record) = self.records.iter_mut().find(|r| r.challenge == *challenge) else {
+            return;
+        };
+        match kind {
+            AttemptKind::Official => record.official = result,
+            AttemptKind::Practice if result.outcome == Outcome::Won => {
+                let best = record
+                    .best_practice_time
+                    .map_or(result.time, |t| t.min(result.time));
+                record.best_practice_time = Some(best);
+            }
+            AttemptKind::Practice => (),
+        }
+    }
+}
@@ -1,13 +1,60 @@
+use bevy::prelude::Entity;
+
 use crate::components::Coordinates;
 
 #[derive(Debug, Copy, Clone)]
-pub struct TileTriggerEvent(pub Coordinates);
+pub struct TileTriggerEvent {
+    pub board: Entity,
+    pub coordinates: Coordinates,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct BoardCompletedEvent {
+    pub board: Entity,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct BombExplosionEvent {
+    pub board: Entity,
+}
 
 #[derive(Debug, Copy, Clone)]
-pub struct BoardCompletedEvent;
+pub struct TileMarkEvent {
+    pub board: Entity,
+    pub coordinates: Coordinates,
+}
 
 #[derive(Debug, Copy, Clone)]
-pub struct BombExplosionEvent;
+pub struct TileChordEvent {
+    pub board: Entity,
+    pub coordinates: Coordinates,
+}
+
+/// Requests a description of the tile at `coordinates` and its neighbors, announced by the
+/// [`crate::components::BoardAnnouncer`] of the board
+#[derive(Debug, Copy, Clone)]
+pub struct DescribeNeighborhoodEvent {
+    pub board: Entity,
+    pub coordinates: Coordinates,
+}
+
+/// Tiles uncovered by a reveal or a chord, sent along the explosion and completion events
+#[derive(Debug, Copy, Clone)]
+pub struct TilesRevealedEvent {
+    pub board: Entity,
+    /// Number of revealed tiles, more than one for a cascade
+    pub count: usize,
+}
+
+/// Skips the running animations, showing their end at once
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SkipAnimationsEvent;
 
+/// Marks placed or removed on a tile
 #[derive(Debug, Copy, Clone)]
-pub struct TileMarkEvent(pub Coordinates);
+pub struct TileMarkedEvent {
+    pub board: Entity,
+    pub coordinates: Coordinates,
+    /// New mark count, 0 once unflagged
+    pub marks: u8,
+}
@@ -0,0 +1,269 @@
+//! Plain text board layouts, used to author puzzle boards by hand.
+//!
+//! Every non empty line is a row of tiles, the first line being the top row of the board.
+//! Lines starting with `#` are comments. Tiles are written as single characters:
+//!
+//! | Char | Tile                 |
+//! |------|----------------------|
+//! | `.`  | covered safe tile    |
+//! | `*`  | covered mine         |
+//! | `o`  | revealed safe tile   |
+//! | `x`  | revealed mine        |
+//! | `f`  | flagged safe tile    |
+//! | `F`  | flagged mine         |
+//!
+//! Rows containing whitespace are read as whitespace separated tokens instead, allowing
+//! multi-mine tiles and stacked flags: a token is an optional `o` (revealed) or a run of `!`
+//! (one per flag) followed by `.` (no mine) or a run of `*` (one per mine), e.g. `!!**`.
+//! The single character forms are valid tokens too.
+//!
+//! ```text
+//! # A small puzzle
+//! ..*o
+//! .F.o
+//! oooo
+//! ```
+use std::fmt::{self, Display, Formatter};
+
+use crate::components::Coordinates;
+use crate::resources::TileMap;
+
+/// Layout parsing error, with its 1-based `line` and `column`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LayoutError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: LayoutErrorKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LayoutErrorKind {
+    /// The layout has no rows
+    Empty,
+    /// Unknown tile character or token
+    InvalidTile(String),
+    /// A tile holds more mines than [`TileMap::MAX_MINES_PER_TILE`]
+    TooManyMines(usize),
+    /// A row is shorter or longer than the first one
+    RowLength { expected: usize, found: usize },
+    /// The layout is larger than a `TileMap` supports
+    TooLarge,
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            LayoutErrorKind::Empty => write!(f, "layout has no rows"),
+            LayoutErrorKind::InvalidTile(tile) => write!(f, "invalid tile `{}`", tile),
+            LayoutErrorKind::TooManyMines(count) => write!(
+                f,
+                "{} mines on a single tile, at most {} are allowed",
+                count,
+                TileMap::MAX_MINES_PER_TILE
+            ),
+            LayoutErrorKind::RowLength { expected, found } => {
+                write!(f, "row has {} tiles, expected {}", found, expected)
+            }
+            LayoutErrorKind::TooLarge => write!(f, "layout is too large"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// State of a single layout tile
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+struct Cell {
+    mines: u8,
+    revealed: bool,
+    flags: u8,
+}
+
+impl Cell {
+    /// Single character form of the cell, if any
+    fn as_char(&self) -> Option<char> {
+        match (self.mines, self.revealed, self.flags) {
+            (0, false, 0) => Some('.'),
+            (1, false, 0) => Some('*'),
+            (0, true, _) => Some('o'),
+            (1, true, _) => Some('x'),
+            (0, false, 1) => Some('f'),
+            (1, false, 1) => Some('F'),
+            _ => None,
+        }
+    }
+
+    fn parse(token: &str) -> Result<Self, LayoutErrorKind> {
+        let invalid = || LayoutErrorKind::InvalidTile(token.to_string());
+        let (state, content) = match token {
+            "o" => ("o", "."),
+            "x" => ("o", "*"),
+            "f" => ("!", "."),
+            "F" => ("!", "*"),
+            _ => token.split_at(token.find(['.', '*']).ok_or_else(invalid)?),
+        };
+        let revealed = state == "o";
+        if !revealed && state.chars().any(|c| c != '!') {
+            return Err(invalid());
+        }
+        let mines = match content {
+            "." => 0,
+            _ if content.chars().all(|c| c == '*') => content.len(),
+            _ => return Err(invalid()),
+        };
+        if mines > TileMap::MAX_MINES_PER_TILE as usize {
+            return Err(LayoutErrorKind::TooManyMines(mines));
+        }
+        let flags = if revealed { 0 } else { state.len() };
+        if flags > TileMap::MAX_MINES_PER_TILE as usize {
+            return Err(invalid());
+        }
+        Ok(Self {
+            mines: mines as u8,
+            revealed,
+            flags: flags as u8,
+        })
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.revealed {
+            write!(f, "o")?;
+        } else {
+            write!(f, "{}", "!".repeat(self.flags as usize))?;
+        }
+        match self.mines {
+            0 => write!(f, "."),
+            mines => write!(f, "{}", "*".repeat(mines as usize)),
+        }
+    }
+}
+
+/// Parses a text layout into a square tile map with its neighbor bomb counts computed.
+///
+/// The map allows as many mines per tile as the most mined tile holds.
+pub fn parse_layout(text: &str) -> Result<TileMap, LayoutError> {
+    let mut rows: Vec<Vec<Cell>> = Vec::new();
+    let mut last_line = 0;
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        last_line = line_number;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let error = |column: usize, kind| LayoutError {
+            line: line_number,
+            column,
+            kind,
+        };
+        let mut row = Vec::new();
+        let tokenized = trimmed.contains(char::is_whitespace);
+        for (offset, token) in tokens(line, tokenized) {
+            let column = line[..offset].chars().count() + 1;
+            row.push(Cell::parse(token).map_err(|kind| error(column, kind))?);
+        }
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                let column = line.trim_end().chars().count() + 1;
+                let kind = LayoutErrorKind::RowLength {
+                    expected: first.len(),
+                    found: row.len(),
+                };
+                return Err(error(column, kind));
+            }
+        }
+        rows.push(row);
+    }
+    let Some(width) = rows.first().map(Vec::len) else {
+        return Err(LayoutError {
+            line: last_line.max(1),
+            column: 1,
+            kind: LayoutErrorKind::Empty,
+        });
+    };
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(rows.len())) else {
+        return Err(LayoutError {
+            line: 1,
+            column: 1,
+            kind: LayoutErrorKind::TooLarge,
+        });
+    };
+
+    let max_mines = rows.iter().flatten().map(|c| c.mines).max().unwrap_or(0);
+    let mut tile_map = TileMap::empty(width, height).with_max_mines_per_tile(max_mines);
+    // The first row is the top of the board
+    for (row, y) in rows.iter().zip((0..height).rev()) {
+        for (cell, x) in row.iter().zip(0..width) {
+            let coordinates = Coordinates { x, y };
+            tile_map.set_mines_at(coordinates, cell.mines);
+            if cell.revealed {
+                tile_map.reveal(coordinates);
+            } else {
+                tile_map.set_flags_at(coordinates, cell.flags);
+            }
+        }
+    }
+    tile_map.refresh_bomb_counts();
+    Ok(tile_map)
+}
+
+/// Splits a layout `line` in tiles, returning their byte offsets
+fn tokens(line: &str, tokenized: bool) -> Box<dyn Iterator<Item = (usize, &str)> + '_> {
+    if tokenized {
+        Box::new(line.split_whitespace().map(move |token| {
+            // `split_whitespace` yields sub slices of `line`
+            (token.as_ptr() as usize - line.as_ptr() as usize, token)
+        }))
+    } else {
+        Box::new(
+            line.char_indices()
+                .filter(|(_, c)| !c.is_whitespace())
+                .map(move |(i, c)| (i, &line[i..i + c.len_utf8()])),
+        )
+    }
+}
+
+/// Writes `tile_map` as a text layout.
+///
+/// Single character tiles are used unless the map has multi-mine tiles or stacked flags.
+pub fn write_layout(tile_map: &TileMap) -> String {
+    let rows: Vec<Vec<Cell>> = (0..tile_map.height())
+        .rev()
+        .map(|y| {
+            (0..tile_map.width())
+                .map(|x| {
+                    let coordinates = Coordinates { x, y };
+                    Cell {
+                        mines: tile_map.mines_at(coordinates),
+                        revealed: tile_map.is_revealed_at(coordinates),
+                        flags: tile_map.flags_at(coordinates),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let compact = rows.iter().flatten().all(|c| c.as_char().is_some());
+    let width = rows
+        .iter()
+        .flatten()
+        .map(|c| c.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let mut buffer = String::new();
+    for row in rows {
+        let line: Vec<String> = row
+            .iter()
+            .map(|cell| match cell.as_char() {
+                Some(c) if compact => c.to_string(),
+                _ => format!("{:<width$}", cell.to_string(), width = width),
+            })
+            .collect();
+        let separator = if compact { "" } else { " " };
+        buffer.push_str(line.join(separator).trim_end());
+        buffer.push('\n');
+    }
+    buffer
+}
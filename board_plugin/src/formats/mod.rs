@@ -0,0 +1,5 @@
+mod layout;
+mod rawvf;
+
+pub use layout::{parse_layout, write_layout, LayoutError, LayoutErrorKind};
+pub use rawvf::{parse_rawvf, write_rawvf, RawVfError, RawVfErrorKind};
@@ -0,0 +1,332 @@
+//! Minesweeper Arbiter and Vienna Minesweeper raw video files (RAWVF), the plain text replay
+//! format read and written by most community tools.
+//!
+//! A file starts with `Key: Value` headers, followed by a `Board:` section holding one line per
+//! row, `*` for mines and `0` for safe tiles, and an `Events:` section holding one mouse event
+//! per line:
+//!
+//! ```text
+//! RawVF_Version: Rev5
+//! Width: 3
+//! Height: 2
+//! Mines: 1
+//! Board:
+//! *00
+//! 000
+//! Events:
+//! 0.00 lc 3 2 (40 24)
+//! 0.02 lr 3 2 (40 24)
+//! ```
+//!
+//! Events are written as `time event column row (x y)`, columns and rows being 1-based from the
+//! top left corner and `x y` the cursor position in pixels. Only button presses and releases
+//! (`lc`, `lr`, `rc`, `rr`, `mc`, `mr`) are mapped to [`Action`]s:
+//! - a left release reveals the tile
+//! - a right press marks the tile
+//! - releasing a button while the other one is held, or a middle release, chords the tile
+//!
+//! Other events such as cursor moves are ignored. RAWVF boards are square, non wrapping and
+//! hold a single mine per tile: multi-mine tiles are written as single mines.
+use std::fmt::{self, Display, Formatter, Write};
+
+use crate::components::Coordinates;
+use crate::resources::{Action, Replay, TileMap, TimedAction};
+
+/// Width in pixels of a tile in the written cursor positions
+const PIXELS_PER_TILE: u32 = 16;
+
+/// RAWVF parsing error, with its 1-based `line`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawVfError {
+    pub line: usize,
+    pub kind: RawVfErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawVfErrorKind {
+    /// A required header is missing
+    MissingHeader(&'static str),
+    /// A header value isn't a valid number
+    InvalidHeader(String),
+    /// The file has no `Board:` section
+    MissingBoard,
+    /// Unknown board tile character
+    InvalidTile(char),
+    /// A board row doesn't match the `Width` header
+    RowLength { expected: usize, found: usize },
+    /// The board row count doesn't match the `Height` header
+    RowCount { expected: usize, found: usize },
+    /// The board mine count doesn't match the `Mines` header
+    MineCount { expected: usize, found: usize },
+    /// Malformed event line
+    InvalidEvent(String),
+    /// An event targets a tile out of the board
+    OutOfBounds { column: u16, row: u16 },
+}
+
+impl Display for RawVfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            RawVfErrorKind::MissingHeader(header) => write!(f, "missing `{}` header", header),
+            RawVfErrorKind::InvalidHeader(header) => write!(f, "invalid `{}` value", header),
+            RawVfErrorKind::MissingBoard => write!(f, "missing `Board:` section"),
+            RawVfErrorKind::InvalidTile(tile) => write!(f, "invalid tile `{}`", tile),
+            RawVfErrorKind::RowLength { expected, found } => {
+                write!(f, "row has {} tiles, expected {}", found, expected)
+            }
+            RawVfErrorKind::RowCount { expected, found } => {
+                write!(f, "board has {} rows, expected {}", found, expected)
+            }
+            RawVfErrorKind::MineCount { expected, found } => {
+                write!(f, "board has {} mines, expected {}", found, expected)
+            }
+            RawVfErrorKind::InvalidEvent(event) => write!(f, "invalid event `{}`", event),
+            RawVfErrorKind::OutOfBounds { column, row } => {
+                write!(f, "tile {} {} is out of the board", column, row)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RawVfError {}
+
+/// File section being parsed
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Section {
+    Headers,
+    Board,
+    Events,
+}
+
+/// Mouse button state, turning button events into actions
+#[derive(Debug, Default)]
+struct Buttons {
+    left: bool,
+    right: bool,
+    /// A chord happened, the next release of the other button is ignored
+    chorded: bool,
+}
+
+impl Buttons {
+    fn event(&mut self, event: &str, coordinates: Coordinates) -> Option<Action> {
+        match event {
+            "lc" => self.left = true,
+            "rc" => {
+                self.right = true;
+                if !self.left {
+                    return Some(Action::Mark(coordinates));
+                }
+            }
+            "lr" | "rr" => {
+                let other = if event == "lr" {
+                    self.left = false;
+                    self.right
+                } else {
+                    self.right = false;
+                    self.left
+                };
+                if other {
+                    self.chorded = true;
+                    return Some(Action::Chord(coordinates));
+                }
+                if std::mem::take(&mut self.chorded) {
+                    return None;
+                }
+                if event == "lr" {
+                    return Some(Action::Reveal(coordinates));
+                }
+            }
+            "mr" => return Some(Action::Chord(coordinates)),
+            _ => (),
+        }
+        None
+    }
+}
+
+/// Parses a RAWVF file into the board and its replayed actions
+pub fn parse_rawvf(text: &str) -> Result<Replay, RawVfError> {
+    let mut section = Section::Headers;
+    let (mut width, mut height, mut mines) = (None, None, None);
+    let mut rows: Vec<(usize, &str)> = Vec::new();
+    let mut events: Vec<(usize, &str)> = Vec::new();
+    let mut last_line = 0;
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        last_line = line_number;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        match trimmed {
+            "Board:" => section = Section::Board,
+            "Events:" => section = Section::Events,
+            _ => match section {
+                Section::Headers => {
+                    let Some((key, value)) = trimmed.split_once(':') else {
+                        continue;
+                    };
+                    let target = match key.trim() {
+                        "Width" => &mut width,
+                        "Height" => &mut height,
+                        "Mines" => &mut mines,
+                        _ => continue,
+                    };
+                    let value = value.trim().parse::<u16>().map_err(|_| RawVfError {
+                        line: line_number,
+                        kind: RawVfErrorKind::InvalidHeader(key.trim().to_string()),
+                    })?;
+                    *target = Some(value);
+                }
+                Section::Board => rows.push((line_number, trimmed)),
+                Section::Events => events.push((line_number, trimmed)),
+            },
+        }
+    }
+    let missing = |header| RawVfError {
+        line: last_line.max(1),
+        kind: RawVfErrorKind::MissingHeader(header),
+    };
+    let width = width.ok_or_else(|| missing("Width"))?;
+    let height = height.ok_or_else(|| missing("Height"))?;
+    let mines = mines.ok_or_else(|| missing("Mines"))?;
+    if rows.is_empty() {
+        return Err(RawVfError {
+            line: last_line.max(1),
+            kind: RawVfErrorKind::MissingBoard,
+        });
+    }
+    if rows.len() != height as usize {
+        return Err(RawVfError {
+            line: rows[0].0,
+            kind: RawVfErrorKind::RowCount {
+                expected: height as usize,
+                found: rows.len(),
+            },
+        });
+    }
+
+    let mut tile_map = TileMap::empty(width, height);
+    // The first row is the top of the board
+    for (&(line, row), y) in rows.iter().zip((0..height).rev()) {
+        let error = |kind| RawVfError { line, kind };
+        if row.chars().count() != width as usize {
+            return Err(error(RawVfErrorKind::RowLength {
+                expected: width as usize,
+                found: row.chars().count(),
+            }));
+        }
+        for (tile, x) in row.chars().zip(0..width) {
+            match tile {
+                '*' => tile_map.set_mines_at(Coordinates { x, y }, 1),
+                '0' => (),
+                _ => return Err(error(RawVfErrorKind::InvalidTile(tile))),
+            }
+        }
+    }
+    tile_map.refresh_bomb_counts();
+    if tile_map.mined_count() != mines as usize {
+        return Err(RawVfError {
+            line: rows[0].0,
+            kind: RawVfErrorKind::MineCount {
+                expected: mines as usize,
+                found: tile_map.mined_count(),
+            },
+        });
+    }
+
+    let mut buttons = Buttons::default();
+    let mut actions = Vec::new();
+    for (line, event) in events {
+        let error = |kind| RawVfError { line, kind };
+        let invalid = || error(RawVfErrorKind::InvalidEvent(event.to_string()));
+        let mut fields = event.split_whitespace();
+        let time = fields
+            .next()
+            .and_then(|t| t.parse::<f32>().ok())
+            .ok_or_else(invalid)?;
+        let name = fields.next().ok_or_else(invalid)?;
+        if !matches!(name, "lc" | "lr" | "rc" | "rr" | "mc" | "mr") {
+            continue;
+        }
+        let mut position = || fields.next().and_then(|v| v.parse::<u16>().ok());
+        let (Some(column), Some(row)) = (position(), position()) else {
+            return Err(invalid());
+        };
+        if !(1..=width).contains(&column) || !(1..=height).contains(&row) {
+            return Err(error(RawVfErrorKind::OutOfBounds { column, row }));
+        }
+        let coordinates = Coordinates {
+            x: column - 1,
+            y: height - row,
+        };
+        if let Some(action) = buttons.event(name, coordinates) {
+            actions.push(TimedAction { time, action });
+        }
+    }
+    Ok(Replay { tile_map, actions })
+}
+
+/// Writes a replay as a RAWVF file.
+///
+/// Reveals are written as left clicks, marks as right clicks and chords as both buttons
+/// pressed at once. Actions outside the board are skipped, as they have no effect.
+pub fn write_rawvf(replay: &Replay) -> String {
+    let tile_map = &replay.tile_map;
+    let (width, height) = (tile_map.width(), tile_map.height());
+    let level = match (width, height, tile_map.mined_count()) {
+        (8, 8, 10) | (9, 9, 10) => "Beginner",
+        (16, 16, 40) => "Intermediate",
+        (30, 16, 99) => "Expert",
+        _ => "Custom",
+    };
+    let time = replay.actions.last().map_or(0., |a| a.time);
+
+    let mut out = String::new();
+    out.push_str("RawVF_Version: Rev5\n");
+    out.push_str("Program: Mine Sweeper\n");
+    writeln!(out, "Level: {}", level).unwrap();
+    writeln!(out, "Width: {}", width).unwrap();
+    writeln!(out, "Height: {}", height).unwrap();
+    writeln!(out, "Mines: {}", tile_map.mined_count()).unwrap();
+    out.push_str("Marks: Off\n");
+    writeln!(out, "Time: {:.2}", time).unwrap();
+    out.push_str("Board:\n");
+    for y in (0..height).rev() {
+        for x in 0..width {
+            let tile = if tile_map.is_bomb_at(Coordinates { x, y }) {
+                '*'
+            } else {
+                '0'
+            };
+            out.push(tile);
+        }
+        out.push('\n');
+    }
+    out.push_str("Events:\n");
+    for timed in &replay.actions {
+        let c = timed.action.coordinates();
+        if c.x >= width || c.y >= height {
+            continue;
+        }
+        let events: &[&str] = match timed.action {
+            Action::Reveal(_) => &["lc", "lr"],
+            Action::Mark(_) => &["rc", "rr"],
+            Action::Chord(_) => &["lc", "rc", "lr", "rr"],
+        };
+        let (column, row) = (c.x as u32 + 1, (height - c.y) as u32);
+        let (px, py) = (
+            (column - 1) * PIXELS_PER_TILE + PIXELS_PER_TILE / 2,
+            (row - 1) * PIXELS_PER_TILE + PIXELS_PER_TILE / 2,
+        );
+        for event in events {
+            writeln!(
+                out,
+                "{:.2} {} {} {} ({} {})",
+                timed.time, event, column, row, px, py
+            )
+            .unwrap();
+        }
+    }
+    out
+}
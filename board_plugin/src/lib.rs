@@ -1,26 +1,158 @@
 mod bounds;
 pub mod components;
-mod events;
+pub mod daily;
+pub mod events;
+pub mod formats;
+pub mod net;
 pub mod resources;
+pub mod settings;
+pub mod simulation;
 mod systems;
 
+use bevy::a11y::Focus;
+use bevy::ecs::system::EntityCommands;
 use bevy::log;
 use bevy::prelude::*;
+use bevy::render::camera::Viewport;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
 use bevy::utils::HashMap;
+use bevy::window::PrimaryWindow;
 use events::BoardCompletedEvent;
+use net::{NetClient, ServerMessage, SpectatorClient, SpectatorServer};
 use resources::tile::Tile;
 use resources::tile_map::TileMap;
 use resources::BoardAssets;
 use resources::BoardOptions;
+use resources::SpriteMaterial;
+use resources::{
+    AnimationSettings, AudioSettings, BoardTheme, Catalog, CatalogLoader, Locale, Localization,
+    SkinTile, Theme, ThemeLoader,
+};
 
 use crate::bounds::Bounds2;
 use crate::components::*;
 use crate::events::BombExplosionEvent;
+use crate::events::TileChordEvent;
 use crate::events::TileMarkEvent;
 use crate::events::TileTriggerEvent;
+use crate::events::{
+    DescribeNeighborhoodEvent, SkipAnimationsEvent, TileMarkedEvent, TilesRevealedEvent,
+};
 use crate::resources::Board;
 use crate::resources::BoardPosition;
 use crate::resources::TileSize;
+use crate::resources::Topology;
+
+/// Shared sprite settings of the board tiles
+struct TileSprites {
+    /// Tile size, padding included
+    size: f32,
+    /// Tile and cover sprite size
+    extent: Vec2,
+    /// Bomb and bomb counter size
+    content_size: f32,
+    tile_texture: Handle<Image>,
+    cover_texture: Handle<Image>,
+    /// Plain texture shaped like a tile
+    mask_texture: Handle<Image>,
+    /// Sprite sheet drawing the tiles and covers, see [`resources::TileSkin`]
+    skin: Option<Handle<TextureAtlas>>,
+    /// The skin draws the bomb counters
+    skin_numbers: bool,
+}
+
+impl TileSprites {
+    fn new(
+        topology: Topology,
+        size: f32,
+        padding: f32,
+        board_assets: &BoardAssets,
+        tile_mask: Option<Handle<Image>>,
+    ) -> Self {
+        // Untextured tiles are shaped by the topology tile mask
+        let texture = |material: &SpriteMaterial| match &tile_mask {
+            Some(mask) if material.texture == DEFAULT_IMAGE_HANDLE.typed() => mask.clone(),
+            _ => material.texture.clone(),
+        };
+        Self {
+            size,
+            extent: topology.tile_extent(size) - topology.tile_extent(padding),
+            content_size: size - padding,
+            tile_texture: texture(&board_assets.tile_material),
+            cover_texture: texture(&board_assets.covered_tile_material),
+            mask_texture: tile_mask
+                .clone()
+                .unwrap_or_else(|| DEFAULT_IMAGE_HANDLE.typed()),
+            skin: board_assets.skin.as_ref().map(|skin| skin.atlas.clone()),
+            skin_numbers: board_assets.skin.as_ref().is_some_and(|skin| skin.numbers),
+        }
+    }
+
+    /// Tile or cover sprite, drawn from the skin sheet if any
+    fn spawn<'w, 's, 'a>(
+        &self,
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+        (material, texture, cell): (&SpriteMaterial, &Handle<Image>, SkinTile),
+        translation: Vec3,
+        alpha: f32,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let transform = Transform::from_translation(translation);
+        match &self.skin {
+            Some(atlas) => parent.spawn(SpriteSheetBundle {
+                sprite: TextureAtlasSprite {
+                    index: cell.index(),
+                    color: Color::WHITE.with_a(alpha),
+                    custom_size: Some(self.extent),
+                    ..Default::default()
+                },
+                texture_atlas: atlas.clone(),
+                transform,
+                ..Default::default()
+            }),
+            None => parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: material.color.with_a(alpha),
+                    custom_size: Some(self.extent),
+                    ..Default::default()
+                },
+                texture: texture.clone(),
+                transform,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// Skin sheet cell of a tile, `numbers` drawing the bomb counters from the sheet
+fn skin_content(tile: Tile, revealed: bool, numbers: bool) -> SkinTile {
+    match tile {
+        // Mines are only revealed when exploding, the other ones being uncovered after it
+        Tile::Bomb(_) if revealed => SkinTile::ExplodedMine,
+        Tile::Bomb(_) => SkinTile::Mine,
+        Tile::BombNeighbor(count) if count <= 8 && numbers => SkinTile::Revealed(count),
+        // Larger counts of multi mine boards are written over an empty tile
+        Tile::BombNeighbor(_) | Tile::Empty => SkinTile::Revealed(0),
+    }
+}
+
+/// Alpha of the ghost tiles of wrapping boards
+pub(crate) const GHOST_ALPHA: f32 = 0.35;
+
+/// Tint of the exploded mine tiles of unskinned boards
+pub(crate) const EXPLODED_COLOR: Color = Color::rgb(0.9, 0.15, 0.1);
+
+/// Entities requesting a board, see [`BoardPlugin::spawn_boards`]
+type BoardRequests<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static BoardOptions,
+        Option<&'static BoardAssets>,
+        Option<&'static BoardPlayer>,
+    ),
+    Without<Board>,
+>;
 
 pub struct BoardPlugin<T> {
     pub running_state: T,
@@ -30,22 +162,112 @@ impl<T: States> Plugin for BoardPlugin<T> {
     fn build(&self, app: &mut App) {
         app
             // When the running states comes into the stack we load a board
-            .add_system(Self::create_board.in_schedule(OnEnter(self.running_state.clone())))
+            .add_system(
+                Self::create_board
+                    .run_if(not(resource_exists::<NetClient>()))
+                    .run_if(not(resource_exists::<SpectatorClient>()))
+                    .in_schedule(OnEnter(self.running_state.clone())),
+            )
+            // Networked and spectated boards come from their server
+            .add_system(
+                systems::net::join_server
+                    .run_if(resource_exists::<NetClient>())
+                    .in_schedule(OnEnter(self.running_state.clone())),
+            )
             // We handle input and trigger events only if the state is active
             .add_systems(
                 (
                     systems::input::input_handling,
+                    systems::input::cursor_input_handling,
                     systems::uncover::trigger_event_handler,
+                    systems::uncover::chord_event_handler,
                 )
                     .in_set(OnUpdate(self.running_state.clone())),
             )
+            .add_systems(
+                (
+                    systems::net::receive_server_messages,
+                    systems::net::send_actions,
+                    systems::net::send_cursor,
+                )
+                    .distributive_run_if(resource_exists::<NetClient>())
+                    .in_set(OnUpdate(self.running_state.clone())),
+            )
+            .add_systems(
+                (
+                    systems::net::spawn_remote_board,
+                    systems::net::update_remote_players,
+                    systems::net::apply_remote_updates,
+                )
+                    .after(systems::net::receive_server_messages)
+                    .in_set(OnUpdate(self.running_state.clone())),
+            )
+            .add_system(
+                systems::spectate::publish_game
+                    .run_if(resource_exists::<SpectatorServer>())
+                    .after(systems::uncover::trigger_event_handler)
+                    .after(systems::uncover::chord_event_handler)
+                    .after(systems::mark::mark_tiles)
+                    .in_set(OnUpdate(self.running_state.clone())),
+            )
+            .add_system(
+                systems::spectate::receive_spectated_game
+                    .run_if(resource_exists::<SpectatorClient>())
+                    .in_set(OnUpdate(self.running_state.clone())),
+            )
             // We handle uncovering even if the state is inactive
-            .add_systems((systems::uncover::uncover_tiles, systems::mark::mark_tiles))
+            .add_systems((
+                Self::spawn_boards,
+                Self::update_board_viewports,
+                systems::highlight::highlight_tiles,
+                systems::uncover::tick_uncover_delays,
+                systems::uncover::uncover_tiles.after(systems::uncover::tick_uncover_delays),
+                systems::mark::mark_tiles,
+                systems::net::update_remote_cursors,
+                systems::spectate::show_spectated_mines,
+                systems::theme::apply_theme.run_if(resource_exists::<BoardTheme>()),
+                systems::audio::play_board_sounds.run_if(resource_exists::<Audio>()),
+            ))
+            .add_systems((
+                systems::animation::animate_explosions
+                    .after(systems::uncover::trigger_event_handler)
+                    .after(systems::uncover::chord_event_handler)
+                    .after(systems::net::apply_remote_updates),
+                systems::animation::celebrate_wins,
+                systems::animation::shake_boards,
+                systems::animation::update_confetti,
+                systems::animation::skip_animations
+                    .after(systems::animation::animate_explosions)
+                    .before(systems::uncover::tick_uncover_delays),
+            ))
+            .add_systems((
+                systems::localization::load_catalogs,
+                systems::accessibility::label_tiles.after(systems::localization::load_catalogs),
+                systems::accessibility::focus_cursor_tiles.run_if(resource_exists::<Focus>()),
+                systems::accessibility::announce_board_events
+                    .after(systems::localization::load_catalogs),
+                systems::accessibility::update_announcers
+                    .after(systems::accessibility::announce_board_events),
+            ))
             .add_system(Self::cleanup_board.in_schedule(OnExit(self.running_state.clone())))
             .add_event::<TileTriggerEvent>()
             .add_event::<TileMarkEvent>()
+            .add_event::<TileChordEvent>()
             .add_event::<BombExplosionEvent>()
-            .add_event::<BoardCompletedEvent>();
+            .add_event::<BoardCompletedEvent>()
+            .add_event::<TilesRevealedEvent>()
+            .add_event::<TileMarkedEvent>()
+            .add_event::<SkipAnimationsEvent>()
+            .add_event::<DescribeNeighborhoodEvent>()
+            .add_event::<ServerMessage>()
+            .init_resource::<AudioSettings>()
+            .init_resource::<AnimationSettings>()
+            .init_resource::<Locale>()
+            .init_resource::<Localization>()
+            .add_asset::<Theme>()
+            .init_asset_loader::<ThemeLoader>()
+            .add_asset::<Catalog>()
+            .init_asset_loader::<CatalogLoader>();
         log::info!("Loaded board plugin");
 
         #[cfg(feature = "debug")]
@@ -59,188 +281,454 @@ impl<T: States> Plugin for BoardPlugin<T> {
 }
 
 impl<T> BoardPlugin<T> {
-    /// System to generate the complete board
+    /// System requesting the boards of the [`BoardOptions`] resource, one per player
     pub fn create_board(
         mut commands: Commands,
         board_options: Option<Res<BoardOptions>>,
-        windows: Query<&Window>,
-        board_assets: Res<BoardAssets>,
+        windows: Query<&Window, With<PrimaryWindow>>,
     ) {
-        let options = match board_options {
+        let mut options = match board_options {
             Some(o) => o.clone(),
             None => BoardOptions::default(),
         };
-        let mut tile_map = TileMap::empty(options.map_size.0, options.map_size.1);
-        tile_map.set_bombs(options.bomb_count);
-
-        #[cfg(feature = "debug")]
-        log::info!("{}", tile_map.console_output());
+        let players = match options.players.is_empty() {
+            true => vec![BoardPlayer::default()],
+            false => std::mem::take(&mut options.players),
+        };
+        let player_count = players.len();
+        if player_count > 1 {
+            // Every player plays the same layout
+            options.seed = Some(options.seed.unwrap_or_else(rand::random));
+            // Split screen boards share the window width, and are laid out far enough apart
+            // for each camera to only show its own
+            if let Ok(window) = windows.get_single() {
+                options.tile_size = Self::split_screen_tile_size(&options, window, player_count);
+            }
+        }
+        let spacing = windows.get_single().map_or(0., |w| w.width());
+        for (index, player) in players.into_iter().enumerate() {
+            let split_offset = Vec3::X * index as f32 * spacing;
+            let position = match options.position {
+                BoardPosition::Centered { offset } => BoardPosition::Centered {
+                    offset: offset + split_offset,
+                },
+                BoardPosition::Custom(p) => BoardPosition::Custom(p + split_offset),
+            };
+            let name = player.name.clone();
+            let board = commands
+                .spawn(BoardOptions {
+                    position,
+                    players: Vec::new(),
+                    ..options.clone()
+                })
+                .insert(player)
+                .id();
+            if player_count > 1 {
+                commands
+                    .spawn(Camera2dBundle {
+                        camera: Camera {
+                            // Rendered before the app cameras, which may overlay a HUD
+                            order: index as isize - player_count as isize,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .insert(BoardCamera { board, index })
+                    .insert(Name::new(format!("{} Camera", name)));
+            }
+        }
+    }
 
-        let window = windows.single();
-        let tile_size = match options.tile_size {
-            TileSize::Fixed(size) => size,
+    /// Fixes the tile size of adaptive split screen boards to fit a window slice
+    fn split_screen_tile_size(
+        options: &BoardOptions,
+        window: &Window,
+        player_count: usize,
+    ) -> TileSize {
+        match options.tile_size {
+            TileSize::Fixed(size) => TileSize::Fixed(size),
             TileSize::Adaptive { min, max } => {
-                Self::adaptive_tile_size(window, (min, max), (tile_map.width(), tile_map.height()))
+                let map_size = options
+                    .generate_tile_map(&mut rand::thread_rng())
+                    .map_or(options.map_size, |m| (m.width(), m.height()));
+                TileSize::Fixed(Self::adaptive_tile_size(
+                    Vec2::new(window.width() / player_count as f32, window.height()),
+                    (min, max),
+                    options.topology.board_size(map_size, 1.),
+                ))
             }
-        };
+        }
+    }
 
-        let board_size = Vec2::new(
-            tile_map.width() as f32 * tile_size,
-            tile_map.height() as f32 * tile_size,
-        );
-        #[cfg(feature = "debug")]
-        log::info!("Board size: {:?}", board_size);
+    /// System building a board on every entity with a [`BoardOptions`] component and no
+    /// [`Board`] yet.
+    ///
+    /// The entity may hold its own [`BoardAssets`] and [`BoardPlayer`], the [`BoardAssets`]
+    /// resource and mouse input being used otherwise.
+    pub fn spawn_boards(
+        mut commands: Commands,
+        requests: BoardRequests,
+        board_assets: Option<Res<BoardAssets>>,
+        windows: Query<&Window, With<PrimaryWindow>>,
+        mut images: ResMut<Assets<Image>>,
+        mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    ) {
+        for (board_entity, options, own_assets, player) in requests.iter() {
+            let Some(board_assets) = own_assets.or(board_assets.as_deref()) else {
+                log::error!("Missing board assets for board {:?}", board_entity);
+                continue;
+            };
+            let tile_map = match options.generate_tile_map(&mut options.rng()) {
+                Ok(tile_map) => tile_map,
+                Err(e) => {
+                    log::error!("Invalid options for board {:?}, {}", board_entity, e);
+                    // Not requested again until the options are fixed
+                    commands.entity(board_entity).remove::<BoardOptions>();
+                    continue;
+                }
+            };
+            let map_size = (tile_map.width(), tile_map.height());
 
-        let board_position = match options.position {
-            BoardPosition::Centered { offset } => {
-                Vec3::new(-(board_size.x / 2.), -(board_size.y / 2.), 0.) + offset
-            }
-            BoardPosition::Custom(p) => p,
-        };
-        let mut covered_tiles =
-            HashMap::with_capacity((tile_map.width() * tile_map.height()).into());
-        let mut safe_start = None;
+            #[cfg(feature = "debug")]
+            log::info!("{}", tile_map.console_output());
 
-        let board_entity = commands
-            .spawn(SpriteBundle {
-                visibility: Visibility::Visible,
-                transform: Transform::from_translation(board_position),
-                ..Default::default()
-            })
-            .insert(Name::new("Board"))
-            .with_children(|parent| {
-                parent
-                    .spawn(SpriteBundle {
-                        sprite: Sprite {
-                            color: board_assets.board_material.color,
-                            custom_size: Some(board_size),
+            let tile_size = match options.tile_size {
+                TileSize::Fixed(size) => size,
+                TileSize::Adaptive { min, max } => Self::adaptive_tile_size(
+                    windows
+                        .get_single()
+                        .map_or(Vec2::ZERO, |w| Vec2::new(w.width(), w.height())),
+                    (min, max),
+                    options.topology.board_size(map_size, 1.),
+                ),
+            };
+
+            let board_size = options.topology.board_size(map_size, tile_size);
+            #[cfg(feature = "debug")]
+            log::info!("Board size: {:?}", board_size);
+
+            let board_position = match options.position {
+                BoardPosition::Centered { offset } => {
+                    Vec3::new(-(board_size.x / 2.), -(board_size.y / 2.), 0.) + offset
+                }
+                BoardPosition::Custom(p) => p,
+            };
+            let mut covered_tiles =
+                HashMap::with_capacity(tile_map.width() as usize * tile_map.height() as usize);
+            let mut ghost_covers = HashMap::new();
+            let sprites = TileSprites::new(
+                options.topology,
+                tile_size,
+                options.tile_padding,
+                board_assets,
+                options.topology.tile_mask().map(|image| images.add(image)),
+            );
+            let uses_cursor = player.is_some_and(|p| !matches!(p.input, BoardInput::Mouse));
+            let name = player.map_or("Board".to_string(), |p| format!("{} Board", p.name));
+
+            commands
+                .entity(board_entity)
+                .insert(SpriteBundle {
+                    visibility: Visibility::Visible,
+                    transform: Transform::from_translation(board_position),
+                    ..Default::default()
+                })
+                .insert(Name::new(name))
+                .with_children(|parent| {
+                    parent
+                        .spawn(SpriteBundle {
+                            sprite: Sprite {
+                                color: board_assets.board_material.color,
+                                custom_size: Some(board_size),
+                                ..Default::default()
+                            },
+                            texture: board_assets.board_material.texture.clone(),
+                            transform: Transform::from_xyz(
+                                board_size.x / 2.,
+                                board_size.y / 2.,
+                                0.,
+                            ),
                             ..Default::default()
-                        },
-                        texture: board_assets.board_material.texture.clone(),
-                        transform: Transform::from_xyz(board_size.x / 2., board_size.y / 2., 0.),
-                        ..Default::default()
-                    })
-                    .insert(Name::new("Background"));
-            })
-            .with_children(|parent| {
-                Self::spawn_tiles(
-                    parent,
-                    &tile_map,
-                    tile_size,
-                    options.tile_padding,
-                    &board_assets,
-                    &mut covered_tiles,
-                    &mut safe_start,
-                );
-            })
-            .id();
-        commands.insert_resource(Board {
-            tile_map,
-            bounds: Bounds2 {
-                position: board_position.truncate(),
-                size: board_size,
-            },
-            tile_size,
-            covered_tiles,
-            entity: board_entity,
-            marked_tiles: Vec::new(),
-        });
+                        })
+                        .insert(Name::new("Background"));
+                })
+                .with_children(|parent| {
+                    Self::spawn_tiles(
+                        parent,
+                        &tile_map,
+                        &sprites,
+                        board_assets,
+                        &mut covered_tiles,
+                    );
+                    if tile_map.is_wrapping() {
+                        Self::spawn_ghost_tiles(
+                            parent,
+                            &tile_map,
+                            &sprites,
+                            board_assets,
+                            &mut ghost_covers,
+                        );
+                    }
+                    if uses_cursor {
+                        Self::spawn_cursor(parent, &tile_map, &sprites);
+                    }
+                    parent
+                        .spawn(BoardAnnouncer::default())
+                        .insert(Name::new("Announcer"));
+                });
+            if player.is_none() {
+                commands.entity(board_entity).insert(BoardPlayer::default());
+            }
 
-        if options.safe_start {
-            if let Some(entity) = safe_start {
-                commands.entity(entity).insert(Uncover);
+            // Loaded layouts may already have revealed tiles
+            if options.safe_start && tile_map.revealed_count() == 0 {
+                let safe_start = tile_map.iter().find(|(_, tile)| *tile == Tile::Empty);
+                if let Some((coordinates, _)) = safe_start {
+                    tile_trigger_ewr.send(TileTriggerEvent {
+                        board: board_entity,
+                        coordinates,
+                    });
+                }
             }
+
+            commands.entity(board_entity).insert(Board {
+                tile_map,
+                bounds: Bounds2 {
+                    position: board_position.truncate(),
+                    size: board_size,
+                },
+                tile_size,
+                covered_tiles,
+                ghost_covers,
+            });
         }
     }
 
-    fn adaptive_tile_size(
-        window: &Window,
-        (min, max): (f32, f32),
-        (width, height): (u16, u16),
-    ) -> f32 {
-        let max_width = window.width() / width as f32;
-        let max_height = window.height() / height as f32;
+    /// Computes the tile size fitting the window `area`, `board_size` being the size of the board
+    /// with unit tiles
+    fn adaptive_tile_size(area: Vec2, (min, max): (f32, f32), board_size: Vec2) -> f32 {
+        let max_width = area.x / board_size.x;
+        let max_height = area.y / board_size.y;
         max_width.min(max_height).clamp(min, max)
     }
 
     fn spawn_tiles(
         parent: &mut ChildBuilder,
         tile_map: &TileMap,
-        size: f32,
-        padding: f32,
+        sprites: &TileSprites,
         board_assets: &BoardAssets,
         covered_tiles: &mut HashMap<Coordinates, Entity>,
-        safe_start_entity: &mut Option<Entity>,
     ) {
-        for (y, line) in tile_map.iter().enumerate() {
-            for (x, tile) in line.iter().enumerate() {
+        let topology = tile_map.topology();
+        for (coordinates, tile) in tile_map.iter() {
+            let translation = topology.tile_center(coordinates, sprites.size).extend(1.);
+            let (mut cmd, cover) = Self::spawn_tile(
+                parent,
+                tile_map,
+                coordinates,
+                translation,
+                sprites,
+                board_assets,
+                1.,
+            );
+            cmd.insert(Name::new(format!("Tile {}", coordinates)))
+                .insert(coordinates);
+            match tile {
+                Tile::Bomb(count) => {
+                    cmd.insert(Bomb { count });
+                }
+                Tile::BombNeighbor(v) => {
+                    cmd.insert(BombNeighbor { count: v });
+                }
+                Tile::Empty => (),
+            };
+            if let Some(cover) = cover {
+                covered_tiles.insert(coordinates, cover);
+            }
+        }
+    }
+
+    /// Spawns translucent copies of the opposite edge tiles around a wrapping board
+    fn spawn_ghost_tiles(
+        parent: &mut ChildBuilder,
+        tile_map: &TileMap,
+        sprites: &TileSprites,
+        board_assets: &BoardAssets,
+        ghost_covers: &mut HashMap<Coordinates, Vec<Entity>>,
+    ) {
+        let topology = tile_map.topology();
+        let (width, height) = (tile_map.width() as i32, tile_map.height() as i32);
+        let period = topology.wrap_period((tile_map.width(), tile_map.height()), sprites.size);
+        // Ghost rings as deep as the neighborhood reaches
+        let depth = tile_map.reach().clamp(1, 2) as i32;
+        for y in -depth..height + depth {
+            for x in -depth..width + depth {
+                let (dx, dy) = (x.div_euclid(width), y.div_euclid(height));
+                if (dx, dy) == (0, 0) {
+                    continue;
+                }
                 let coordinates = Coordinates {
-                    x: x as u16,
-                    y: y as u16,
+                    x: x.rem_euclid(width) as u16,
+                    y: y.rem_euclid(height) as u16,
                 };
-                let mut cmd = parent.spawn(SpriteBundle {
-                    sprite: Sprite {
-                        color: board_assets.tile_material.color,
-                        custom_size: Some(Vec2::splat(size - padding)),
-                        ..Default::default()
-                    },
-                    transform: Transform::from_xyz(
-                        (x as f32 * size) + (size / 2.),
-                        (y as f32 * size) + (size / 2.),
-                        1.,
+                let translation = topology.tile_center(coordinates, sprites.size)
+                    + Vec2::new(dx as f32, dy as f32) * period;
+                let (mut cmd, cover) = Self::spawn_tile(
+                    parent,
+                    tile_map,
+                    coordinates,
+                    translation.extend(1.),
+                    sprites,
+                    board_assets,
+                    GHOST_ALPHA,
+                );
+                cmd.insert(Name::new(format!("Ghost Tile {}", coordinates)));
+                if let Some(cover) = cover {
+                    ghost_covers.entry(coordinates).or_default().push(cover);
+                }
+            }
+        }
+    }
+
+    /// Spawns a tile with its content and cover, returning the tile commands and the cover
+    /// entity if the tile is still covered
+    fn spawn_tile<'w, 's, 'a>(
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+        tile_map: &TileMap,
+        coordinates: Coordinates,
+        translation: Vec3,
+        sprites: &TileSprites,
+        board_assets: &BoardAssets,
+        alpha: f32,
+    ) -> (EntityCommands<'w, 's, 'a>, Option<Entity>) {
+        let tile = tile_map.tile_at(coordinates);
+        let revealed = tile_map.is_revealed_at(coordinates);
+        let exploded = SpriteMaterial {
+            color: EXPLODED_COLOR,
+            ..board_assets.tile_material.clone()
+        };
+        let material = match tile {
+            Tile::Bomb(_) if revealed => &exploded,
+            _ => &board_assets.tile_material,
+        };
+        let mut cover = None;
+        let mut cmd = sprites.spawn(
+            parent,
+            (
+                material,
+                &sprites.tile_texture,
+                skin_content(tile, revealed, sprites.skin_numbers),
+            ),
+            translation,
+            alpha,
+        );
+        cmd.with_children(|parent| {
+            if revealed {
+                return Self::spawn_tile_content(parent, tile, sprites, board_assets, alpha);
+            }
+            let marks = tile_map.flags_at(coordinates);
+            let cell = match marks {
+                0 => SkinTile::Covered,
+                _ => SkinTile::Flag,
+            };
+            let entity = sprites
+                .spawn(
+                    parent,
+                    (
+                        &board_assets.covered_tile_material,
+                        &sprites.cover_texture,
+                        cell,
                     ),
-                    texture: board_assets.tile_material.texture.clone(),
-                    ..Default::default()
+                    Vec3::Z * 2.,
+                    alpha,
+                )
+                .insert(Name::new("Tile Cover"))
+                .with_children(|parent| {
+                    match &sprites.skin {
+                        // The flag cell only misses the count of stacked flags
+                        Some(_) if marks > 1 => {
+                            systems::mark::spawn_mark_count(
+                                parent,
+                                marks,
+                                sprites.size,
+                                board_assets,
+                            );
+                        }
+                        Some(_) => (),
+                        None if marks > 0 => {
+                            systems::mark::spawn_flag(parent, marks, sprites.size, board_assets);
+                        }
+                        None => (),
+                    }
+                    if tile_map.is_questioned_at(coordinates) {
+                        systems::mark::spawn_question_mark(parent, sprites.size, board_assets);
+                    }
+                })
+                .id();
+            cover = Some(entity);
+            Self::spawn_tile_content(parent, tile, sprites, board_assets, alpha);
+        });
+        (cmd, cover)
+    }
+
+    /// Spawns the bomb or bomb counter of a tile
+    fn spawn_tile_content(
+        parent: &mut ChildBuilder,
+        tile: Tile,
+        sprites: &TileSprites,
+        board_assets: &BoardAssets,
+        alpha: f32,
+    ) {
+        match tile {
+            Tile::Bomb(count) => {
+                // Multi mine tiles display their mine count in the corner
+                let counter = (count > 1).then(|| {
+                    let mut bundle = Self::bomb_count_text_bundle(
+                        count,
+                        board_assets,
+                        sprites.content_size / 2.,
+                    );
+                    bundle.text.sections[0].style.color.set_a(alpha);
+                    bundle.transform.translation +=
+                        Vec3::new(sprites.content_size, -sprites.content_size, 0.) / 4.;
+                    bundle
                 });
-                cmd.insert(Name::new(format!("Tile ({}, {})", x, y)))
-                    .insert(coordinates)
+                // Skinned tiles already show the mine
+                if sprites.skin.is_some() {
+                    if let Some(counter) = counter {
+                        parent.spawn(counter);
+                    }
+                    return;
+                }
+                parent
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(sprites.content_size)),
+                            color: board_assets.bomb_material.color.with_a(alpha),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_xyz(0., 0., 1.),
+                        texture: board_assets.bomb_material.texture.clone(),
+                        ..Default::default()
+                    })
                     .with_children(|parent| {
-                        let entity = parent
-                            .spawn(SpriteBundle {
-                                sprite: Sprite {
-                                    custom_size: Some(Vec2::splat(size - padding)),
-                                    color: board_assets.covered_tile_material.color,
-                                    ..Default::default()
-                                },
-                                transform: Transform::from_xyz(0., 0., 2.),
-                                texture: board_assets.covered_tile_material.texture.clone(),
-                                ..Default::default()
-                            })
-                            .insert(Name::new("Tile Cover"))
-                            .id();
-                        covered_tiles.insert(coordinates, entity);
-                        if safe_start_entity.is_none() && *tile == Tile::Empty {
-                            *safe_start_entity = Some(entity);
+                        if let Some(counter) = counter {
+                            parent.spawn(counter);
                         }
                     });
-                match tile {
-                    Tile::Bomb => {
-                        cmd.insert(Bomb);
-                        cmd.with_children(|parent| {
-                            parent.spawn(SpriteBundle {
-                                sprite: Sprite {
-                                    custom_size: Some(Vec2::splat(size - padding)),
-                                    ..Default::default()
-                                },
-                                transform: Transform::from_xyz(0., 0., 1.),
-                                texture: board_assets.bomb_material.texture.clone(),
-                                ..Default::default()
-                            });
-                        });
-                    }
-                    Tile::BombNeighbor(v) => {
-                        cmd.insert(BombNeighbor { count: *v });
-                        cmd.with_children(|parent| {
-                            parent.spawn(Self::bomb_count_text_bundle(
-                                *v,
-                                board_assets,
-                                size - padding,
-                            ));
-                        });
-                    }
-                    Tile::Empty => (),
-                };
             }
+            // Skinned tiles show the counts up to 8
+            Tile::BombNeighbor(v) if v <= 8 && sprites.skin_numbers => (),
+            Tile::BombNeighbor(v) => {
+                let mut bundle =
+                    Self::bomb_count_text_bundle(v, board_assets, sprites.content_size);
+                bundle.text.sections[0].style.color.set_a(alpha);
+                if board_assets.high_contrast {
+                    Self::spawn_contrast_cues(parent, &bundle, v, sprites.content_size);
+                }
+                parent.spawn(bundle);
+            }
+            Tile::Empty => (),
         }
     }
 
@@ -265,8 +753,108 @@ impl<T> BoardPlugin<T> {
         }
     }
 
-    fn cleanup_board(board: Res<Board>, mut commands: Commands) {
-        commands.entity(board.entity).despawn_recursive();
-        commands.remove_resource::<Board>();
+    /// Outlines a bomb counter `text` and repeats its `count` as pips under it, up to 8
+    fn spawn_contrast_cues(parent: &mut ChildBuilder, text: &Text2dBundle, count: u8, size: f32) {
+        let color = text.text.sections[0].style.color;
+        let outline = *Color::BLACK.clone().set_a(color.a());
+        let width = size / 20.;
+        for offset in [
+            Vec2::ONE,
+            Vec2::NEG_ONE,
+            Vec2::new(1., -1.),
+            Vec2::new(-1., 1.),
+        ] {
+            let mut shadow = text.clone();
+            shadow.text.sections[0].style.color = outline;
+            shadow.transform.translation += (offset * width).extend(-0.1);
+            parent.spawn(shadow);
+        }
+        if count > 8 {
+            return;
+        }
+        let pip = size / 12.;
+        let start = -(count as f32 - 1.) * pip * 0.75;
+        for i in 0..count {
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(pip)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(start + i as f32 * pip * 1.5, -size * 0.42, 1.),
+                ..Default::default()
+            });
+        }
+    }
+
+    /// Spawns the tile cursor of a keyboard or gamepad controlled board, starting at its center
+    fn spawn_cursor(parent: &mut ChildBuilder, tile_map: &TileMap, sprites: &TileSprites) {
+        let coordinates = Coordinates {
+            x: tile_map.width() / 2,
+            y: tile_map.height() / 2,
+        };
+        let translation = tile_map
+            .topology()
+            .tile_center(coordinates, sprites.size)
+            .extend(5.);
+        parent
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(sprites.extent),
+                    color: Color::rgba(1., 0.85, 0., 0.5),
+                    ..Default::default()
+                },
+                texture: sprites.mask_texture.clone(),
+                transform: Transform::from_translation(translation),
+                ..Default::default()
+            })
+            .insert(Name::new("Cursor"))
+            .insert(BoardCursor(coordinates));
+    }
+
+    /// Splits the window between the board cameras, centering each one on its board
+    fn update_board_viewports(
+        windows: Query<&Window, With<PrimaryWindow>>,
+        mut cameras: Query<(&mut Camera, &mut Transform, &BoardCamera)>,
+        boards: Query<&Board>,
+    ) {
+        let Ok(window) = windows.get_single() else {
+            return;
+        };
+        let count = cameras.iter().count() as u32;
+        let size = UVec2::new(
+            window.physical_width() / count.max(1),
+            window.physical_height(),
+        );
+        for (mut camera, mut transform, board_camera) in cameras.iter_mut() {
+            if let Ok(board) = boards.get(board_camera.board) {
+                let center = board.bounds.position + board.bounds.size / 2.;
+                if transform.translation.truncate() != center {
+                    transform.translation = center.extend(transform.translation.z);
+                }
+            }
+            let position = UVec2::new(board_camera.index as u32 * size.x, 0);
+            let unchanged = camera
+                .viewport
+                .as_ref()
+                .is_some_and(|v| v.physical_position == position && v.physical_size == size);
+            if !unchanged {
+                camera.viewport = Some(Viewport {
+                    physical_position: position,
+                    physical_size: size,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    fn cleanup_board(
+        mut commands: Commands,
+        boards: Query<Entity, With<Board>>,
+        cameras: Query<Entity, With<BoardCamera>>,
+    ) {
+        for entity in boards.iter().chain(cameras.iter()) {
+            commands.entity(entity).despawn_recursive();
+        }
     }
 }
@@ -0,0 +1,57 @@
+use std::io;
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Mutex;
+
+use bevy::prelude::Resource;
+
+use super::protocol::*;
+
+/// Connection to a [`super::GameServer`].
+///
+/// Inserted as a resource, the boards are played on the server instead of locally: the board of
+/// the server is spawned when entering the running state and player actions are sent to it.
+#[derive(Resource)]
+pub struct NetClient {
+    name: String,
+    stream: TcpStream,
+    /// Messages read by the connection thread
+    receiver: Mutex<Receiver<ServerMessage>>,
+}
+
+impl NetClient {
+    /// Connects to the server at `address`, joining as `name` when entering the running state
+    pub fn connect(address: impl ToSocketAddrs, name: impl Into<String>) -> io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        let receiver = spawn_reader(stream.try_clone()?);
+        Ok(Self {
+            name: name.into(),
+            stream,
+            receiver: Mutex::new(receiver),
+        })
+    }
+
+    /// Player name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn send(&self, message: &ClientMessage) -> io::Result<()> {
+        write_message(&mut &self.stream, message)
+    }
+
+    /// Next received message, if any
+    pub fn try_recv(&self) -> Result<ServerMessage, TryRecvError> {
+        match self.receiver.lock() {
+            Ok(receiver) => receiver.try_recv(),
+            Err(_) => Err(TryRecvError::Disconnected),
+        }
+    }
+}
+
+impl Drop for NetClient {
+    fn drop(&mut self) {
+        // Stops the connection thread
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
@@ -0,0 +1,23 @@
+//! Networked multiplayer games: an authoritative [`GameServer`] holds the board state, while
+//! every client plays a replica of it, sending its actions with [`ClientMessage`]s and applying
+//! the board updates of [`ServerMessage`]s. See [`NetMode`] for the game rules.
+//!
+//! A playing client may also stream its board to read-only spectators with a
+//! [`SpectatorServer`].
+//!
+//! Messages are JSON objects written one per line on a TCP connection.
+mod client;
+mod protocol;
+mod server;
+mod spectator;
+
+pub use client::NetClient;
+pub use protocol::*;
+pub use server::GameServer;
+pub use spectator::{SpectatorClient, SpectatorServer};
+
+/// Address of the server started by the `game_server` binary without arguments
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+/// Default address of a [`SpectatorServer`]
+pub const DEFAULT_SPECTATOR_ADDRESS: &str = "127.0.0.1:7879";
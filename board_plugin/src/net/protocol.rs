@@ -0,0 +1,159 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use bevy::log;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::components::Coordinates;
+use crate::resources::{Action, BoardOptions, TimedAction};
+
+/// Server side identifier of a player
+pub type PlayerId = u32;
+
+/// Multiplayer game rules
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum NetMode {
+    /// Every player clears the same board together, seeing the others cursors
+    #[default]
+    Coop,
+    /// Every player plays its own copy of the same board, racing the others
+    Versus,
+}
+
+/// State of a versus player
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PlayerState {
+    #[default]
+    Playing,
+    Won,
+    Lost,
+}
+
+/// Progress of a versus player on its own board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerProgress {
+    pub player: PlayerId,
+    pub name: String,
+    /// Fraction of the safe tiles revealed, from 0 to 1
+    pub cleared: f32,
+    /// Placed flags, stacked flags included
+    pub flags: usize,
+    pub state: PlayerState,
+    /// Seconds from the first action to the end of the game, once finished
+    pub time: Option<f32>,
+    /// Board 3BV per second of a won game
+    pub three_bv_per_second: Option<f32>,
+}
+
+/// Message sent by a client to the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Joins the game, answered with a [`ServerMessage::Welcome`]
+    Join { name: String },
+    /// Plays an action on the shared board
+    Action(Action),
+    /// Tile under the player cursor, `None` when off the board
+    Cursor(Option<Coordinates>),
+    /// Starts a new board once the current one is cleared or exploded, or every versus player
+    /// finished
+    Restart,
+}
+
+/// Player connected to the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemotePlayer {
+    pub id: PlayerId,
+    pub name: String,
+    pub cursor: Option<Coordinates>,
+}
+
+/// Message sent by the server to its clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// Current board, sent when joining and to every player when a new board starts.
+    ///
+    /// The options source is a [`crate::resources::BoardSource::Layout`] of the board state,
+    /// revealed tiles and marks included, and its seed the seed the board was generated with
+    Welcome {
+        player: PlayerId,
+        mode: NetMode,
+        options: BoardOptions,
+        players: Vec<RemotePlayer>,
+    },
+    /// Another player joined
+    Joined(RemotePlayer),
+    /// A player left
+    Left(PlayerId),
+    /// Tiles revealed by a player action
+    Revealed {
+        player: PlayerId,
+        tiles: Vec<Coordinates>,
+    },
+    /// New mark count of a tile
+    Marked {
+        player: PlayerId,
+        coordinates: Coordinates,
+        marks: u8,
+    },
+    /// Another player moved its cursor, in co-op mode
+    Cursor {
+        player: PlayerId,
+        coordinates: Option<Coordinates>,
+    },
+    /// Progress of a versus player, the local player included
+    Progress(PlayerProgress),
+    /// Final versus ranking, once every player finished: winners by time and 3BV/s first, then
+    /// the other players by cleared fraction
+    Ranking(Vec<PlayerProgress>),
+}
+
+/// Message streamed by a [`super::SpectatorServer`] to its spectators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SpectatorMessage {
+    /// Board being played, the options source being a layout of its state when the stream
+    /// started
+    Board(BoardOptions),
+    /// Action played on the board, timed from the start of the stream
+    Action(TimedAction),
+}
+
+/// Writes `message` as a single JSON line
+pub fn write_message<T: Serialize>(writer: &mut impl Write, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())
+}
+
+/// Reads the next message, `None` once the connection is closed
+pub fn read_message<T: DeserializeOwned>(reader: &mut impl BufRead) -> io::Result<Option<T>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+/// Spawns a thread reading the messages of `stream`, until the connection is closed
+pub(super) fn spawn_reader<T: DeserializeOwned + Send + 'static>(stream: TcpStream) -> Receiver<T> {
+    let mut reader = BufReader::new(stream);
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || loop {
+        match read_message(&mut reader) {
+            Ok(Some(message)) => {
+                if sender.send(message).is_err() {
+                    return;
+                }
+            }
+            Ok(None) => return,
+            Err(e) => {
+                log::error!("Invalid message, {}", e);
+                return;
+            }
+        }
+    });
+    receiver
+}
@@ -0,0 +1,487 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Instant;
+
+use bevy::log;
+use rand::SeedableRng;
+
+use super::protocol::*;
+use crate::components::Coordinates;
+use crate::formats::write_layout;
+use crate::resources::tile::Tile;
+use crate::resources::{Action, BoardOptions, BoardOptionsError, BoardRng, BoardSource, TileMap};
+
+/// Connection event, forwarded by the connection threads to the game loop
+enum ServerEvent {
+    Connected(PlayerId, TcpStream),
+    Message(PlayerId, ClientMessage),
+    Disconnected(PlayerId),
+}
+
+/// Connected client, taking part in the game once joined
+struct Client {
+    stream: TcpStream,
+    name: Option<String>,
+    cursor: Option<Coordinates>,
+    /// Own board, in versus mode
+    game: PlayerGame,
+}
+
+/// Board of a versus player
+struct PlayerGame {
+    tile_map: TileMap,
+    /// Time of the first action
+    started: Option<Instant>,
+    state: PlayerState,
+    /// Game duration in seconds, once finished
+    time: Option<f32>,
+    /// Joined once the round was over, the player waiting for the next one
+    spectating: bool,
+}
+
+impl PlayerGame {
+    fn new(tile_map: TileMap) -> Self {
+        Self {
+            tile_map,
+            started: None,
+            state: PlayerState::Playing,
+            time: None,
+            spectating: false,
+        }
+    }
+}
+
+/// Authoritative multiplayer game server.
+///
+/// Actions are applied in the order they are received. In co-op mode every player plays the
+/// same board, its revealed tiles and marks being broadcast to every player. In versus mode
+/// every player plays its own copy of the round board, the server broadcasting the progress of
+/// each player and the final ranking.
+pub struct GameServer {
+    options: BoardOptions,
+    mode: NetMode,
+    /// Seed of the round board
+    seed: u64,
+    /// Board of the round, shared by the co-op players and copied for each versus player
+    tile_map: TileMap,
+    /// The round is over, actions are ignored until a restart
+    over: bool,
+    /// Final ranking of the versus round, once over
+    ranking: Vec<PlayerProgress>,
+    clients: BTreeMap<PlayerId, Client>,
+}
+
+impl GameServer {
+    /// Co-op server generating its boards with `options`, failing if they can't build one
+    pub fn new(options: BoardOptions) -> Result<Self, BoardOptionsError> {
+        let (seed, tile_map) = Self::new_board(&options)?;
+        Ok(Self {
+            options,
+            mode: NetMode::Coop,
+            seed,
+            tile_map,
+            over: false,
+            ranking: Vec::new(),
+            clients: BTreeMap::new(),
+        })
+    }
+
+    pub fn with_mode(mut self, mode: NetMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Current board of the round
+    pub fn tile_map(&self) -> &TileMap {
+        &self.tile_map
+    }
+
+    /// Generates a board, revealing a safe start if the options request one
+    fn new_board(options: &BoardOptions) -> Result<(u64, TileMap), BoardOptionsError> {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        let mut tile_map = options.generate_tile_map(&mut BoardRng::seed_from_u64(seed))?;
+        if options.safe_start && tile_map.revealed_count() == 0 {
+            let safe_start = tile_map.iter().find(|(_, tile)| *tile == Tile::Empty);
+            if let Some((coordinates, _)) = safe_start {
+                tile_map.reveal_from(coordinates);
+            }
+        }
+        Ok((seed, tile_map))
+    }
+
+    /// Serves the clients connecting to `listener`, until every connection thread stopped
+    pub fn run(mut self, listener: TcpListener) {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || Self::accept(listener, sender));
+        for event in receiver {
+            self.handle(event);
+        }
+    }
+
+    /// Accepts connections, spawning a thread reading the messages of each one
+    fn accept(listener: TcpListener, sender: Sender<ServerEvent>) {
+        for (id, stream) in (0..).zip(listener.incoming()) {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::error!("Failed to accept connection, {}", e);
+                    continue;
+                }
+            };
+            let reader = match stream.try_clone() {
+                Ok(reader) => BufReader::new(reader),
+                Err(e) => {
+                    log::error!("Failed to read connection, {}", e);
+                    continue;
+                }
+            };
+            if sender.send(ServerEvent::Connected(id, stream)).is_err() {
+                return;
+            }
+            let sender = sender.clone();
+            thread::spawn(move || Self::read(id, reader, sender));
+        }
+    }
+
+    fn read(id: PlayerId, mut reader: BufReader<TcpStream>, sender: Sender<ServerEvent>) {
+        loop {
+            match read_message(&mut reader) {
+                Ok(Some(message)) => {
+                    if sender.send(ServerEvent::Message(id, message)).is_err() {
+                        return;
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    log::warn!("Closing connection of player {}, {}", id, e);
+                    break;
+                }
+            }
+        }
+        let _ = sender.send(ServerEvent::Disconnected(id));
+    }
+
+    fn handle(&mut self, event: ServerEvent) {
+        match event {
+            ServerEvent::Connected(id, stream) => {
+                log::info!("Player {} connected", id);
+                let client = Client {
+                    stream,
+                    name: None,
+                    cursor: None,
+                    game: PlayerGame::new(self.tile_map.clone()),
+                };
+                self.clients.insert(id, client);
+            }
+            ServerEvent::Disconnected(id) => {
+                if let Some(client) = self.clients.remove(&id) {
+                    log::info!("Player {} disconnected", id);
+                    if client.name.is_some() {
+                        self.broadcast(&ServerMessage::Left(id), None);
+                        self.check_versus_over();
+                    }
+                }
+            }
+            ServerEvent::Message(id, message) => self.handle_message(id, message),
+        }
+    }
+
+    fn handle_message(&mut self, id: PlayerId, message: ClientMessage) {
+        if let ClientMessage::Join { name } = message {
+            log::info!("Player {} joined as {}", id, name);
+            let spectating = self.mode == NetMode::Versus && self.over;
+            if let Some(client) = self.clients.get_mut(&id) {
+                client.name = Some(name.clone());
+                client.game.spectating = spectating;
+            }
+            self.welcome(id);
+            let player = RemotePlayer {
+                id,
+                name,
+                cursor: None,
+            };
+            self.broadcast(&ServerMessage::Joined(player), Some(id));
+            if self.mode == NetMode::Versus {
+                self.broadcast_progress(id);
+                // The new player gets the progress of the others
+                let others: Vec<PlayerProgress> = self
+                    .clients
+                    .keys()
+                    .filter(|other| **other != id)
+                    .filter_map(|other| self.progress(*other))
+                    .collect();
+                for progress in others {
+                    self.send_to(id, &ServerMessage::Progress(progress));
+                }
+                // Late players watch the end of the round until a restart
+                if spectating {
+                    let ranking = ServerMessage::Ranking(self.ranking.clone());
+                    self.send_to(id, &ranking);
+                }
+            }
+            return;
+        }
+        // Players must join before playing
+        if !self.clients.get(&id).is_some_and(|c| c.name.is_some()) {
+            log::warn!("Ignoring message of player {} before joining", id);
+            return;
+        }
+        match message {
+            ClientMessage::Join { .. } => (),
+            ClientMessage::Action(action) => match self.mode {
+                NetMode::Coop => self.play_coop(id, action),
+                NetMode::Versus => self.play_versus(id, action),
+            },
+            // Versus players play on their own board
+            ClientMessage::Cursor(_) if self.mode == NetMode::Versus => (),
+            ClientMessage::Cursor(coordinates) => {
+                if let Some(client) = self.clients.get_mut(&id) {
+                    client.cursor = coordinates;
+                }
+                let message = ServerMessage::Cursor {
+                    player: id,
+                    coordinates,
+                };
+                self.broadcast(&message, Some(id));
+            }
+            ClientMessage::Restart => {
+                if !self.over {
+                    log::warn!("Player {} asked for a restart during a game", id);
+                    return;
+                }
+                log::info!("Player {} started a new board", id);
+                (self.seed, self.tile_map) = Self::new_board(&self.options)
+                    .expect("The options already built the first board");
+                self.over = false;
+                self.ranking.clear();
+                let players: Vec<PlayerId> = self.clients.keys().copied().collect();
+                for player in &players {
+                    if let Some(client) = self.clients.get_mut(player) {
+                        client.game = PlayerGame::new(self.tile_map.clone());
+                    }
+                    self.welcome(*player);
+                }
+                if self.mode == NetMode::Versus {
+                    for player in players {
+                        self.broadcast_progress(player);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Plays a co-op player action on the shared board, broadcasting its result
+    fn play_coop(&mut self, id: PlayerId, action: Action) {
+        if self.over || !Self::in_board(&self.tile_map, action) {
+            return;
+        }
+        let Some(message) = Self::apply(id, &mut self.tile_map, action) else {
+            return;
+        };
+        if self.tile_map.is_completed() {
+            log::info!("Board completed");
+            self.over = true;
+        } else if Self::exploded(&self.tile_map, &message) {
+            log::info!("Player {} exploded the board", id);
+            self.over = true;
+        }
+        self.broadcast(&message, None);
+    }
+
+    /// Plays a versus player action on its own board, broadcasting its progress
+    fn play_versus(&mut self, id: PlayerId, action: Action) {
+        let Some(client) = self.clients.get_mut(&id) else {
+            return;
+        };
+        let game = &mut client.game;
+        let playing = game.state == PlayerState::Playing && !game.spectating && !self.over;
+        if !playing || !Self::in_board(&game.tile_map, action) {
+            return;
+        }
+        let started = *game.started.get_or_insert_with(Instant::now);
+        let Some(message) = Self::apply(id, &mut game.tile_map, action) else {
+            return;
+        };
+        let state = if Self::exploded(&game.tile_map, &message) {
+            PlayerState::Lost
+        } else if game.tile_map.is_completed() {
+            PlayerState::Won
+        } else {
+            PlayerState::Playing
+        };
+        if state != PlayerState::Playing {
+            game.state = state;
+            game.time = Some(started.elapsed().as_secs_f32());
+            log::info!("Player {} finished: {:?}", id, state);
+        }
+        Self::send(id, client, &message);
+        self.broadcast_progress(id);
+        self.check_versus_over();
+    }
+
+    fn in_board(tile_map: &TileMap, action: Action) -> bool {
+        let c = action.coordinates();
+        c.x < tile_map.width() && c.y < tile_map.height()
+    }
+
+    /// Plays an action, returning the update of its result if anything changed
+    fn apply(id: PlayerId, tile_map: &mut TileMap, action: Action) -> Option<ServerMessage> {
+        match action {
+            Action::Mark(coordinates) => {
+                let marks = tile_map.cycle_flag(coordinates)?;
+                Some(ServerMessage::Marked {
+                    player: id,
+                    coordinates,
+                    marks,
+                })
+            }
+            Action::Reveal(_) | Action::Chord(_) => {
+                let tiles = action.apply(tile_map);
+                (!tiles.is_empty()).then_some(ServerMessage::Revealed { player: id, tiles })
+            }
+        }
+    }
+
+    /// Did the update reveal a mine
+    fn exploded(tile_map: &TileMap, update: &ServerMessage) -> bool {
+        match update {
+            ServerMessage::Revealed { tiles, .. } => tiles.iter().any(|c| tile_map.is_bomb_at(*c)),
+            _ => false,
+        }
+    }
+
+    /// Progress of a joined versus player, none while spectating
+    fn progress(&self, id: PlayerId) -> Option<PlayerProgress> {
+        let client = self.clients.get(&id)?;
+        let game = &client.game;
+        if game.spectating {
+            return None;
+        }
+        let tile_map = &game.tile_map;
+        let safe_tiles =
+            tile_map.width() as usize * tile_map.height() as usize - tile_map.mined_count();
+        let cleared = tile_map
+            .iter()
+            .filter(|(c, tile)| tile_map.is_revealed_at(*c) && !matches!(tile, Tile::Bomb(_)))
+            .count();
+        let three_bv_per_second = match (game.state, game.time) {
+            (PlayerState::Won, Some(time)) => Some(tile_map.three_bv() as f32 / time.max(0.001)),
+            _ => None,
+        };
+        Some(PlayerProgress {
+            player: id,
+            name: client.name.clone()?,
+            cleared: cleared as f32 / safe_tiles.max(1) as f32,
+            flags: tile_map.flagged_count(),
+            state: game.state,
+            time: game.time,
+            three_bv_per_second,
+        })
+    }
+
+    fn broadcast_progress(&mut self, id: PlayerId) {
+        if let Some(progress) = self.progress(id) {
+            self.broadcast(&ServerMessage::Progress(progress), None);
+        }
+    }
+
+    /// Ends the versus round once every player finished, broadcasting the ranking
+    fn check_versus_over(&mut self) {
+        if self.mode != NetMode::Versus || self.over {
+            return;
+        }
+        let mut ranking: Vec<PlayerProgress> = self
+            .clients
+            .keys()
+            .filter_map(|id| self.progress(*id))
+            .collect();
+        if ranking.is_empty() || ranking.iter().any(|p| p.state == PlayerState::Playing) {
+            return;
+        }
+        ranking.sort_by(
+            |a, b| match (a.state == PlayerState::Won, b.state == PlayerState::Won) {
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (true, true) => a
+                    .time
+                    .unwrap_or(f32::MAX)
+                    .total_cmp(&b.time.unwrap_or(f32::MAX))
+                    .then(
+                        b.three_bv_per_second
+                            .unwrap_or(0.)
+                            .total_cmp(&a.three_bv_per_second.unwrap_or(0.)),
+                    ),
+                (false, false) => b.cleared.total_cmp(&a.cleared),
+            },
+        );
+        log::info!("Versus round over");
+        self.over = true;
+        self.ranking = ranking.clone();
+        self.broadcast(&ServerMessage::Ranking(ranking), None);
+    }
+
+    /// Sends the round board to a joined player, its own board in versus mode
+    fn welcome(&mut self, id: PlayerId) {
+        let players = self
+            .clients
+            .iter()
+            .filter(|(other, _)| **other != id)
+            .filter_map(|(other, client)| {
+                Some(RemotePlayer {
+                    id: *other,
+                    name: client.name.clone()?,
+                    cursor: client.cursor,
+                })
+            })
+            .collect();
+        let Some(client) = self.clients.get(&id) else {
+            return;
+        };
+        let tile_map = match self.mode {
+            NetMode::Coop => &self.tile_map,
+            NetMode::Versus => &client.game.tile_map,
+        };
+        let message = ServerMessage::Welcome {
+            player: id,
+            mode: self.mode,
+            options: BoardOptions {
+                source: BoardSource::Layout(write_layout(tile_map)),
+                seed: Some(self.seed),
+                safe_start: false,
+                players: Vec::new(),
+                ..self.options.clone()
+            },
+            players,
+        };
+        self.send_to(id, &message);
+    }
+
+    /// Sends a message to every joined player but `except`
+    fn broadcast(&mut self, message: &ServerMessage, except: Option<PlayerId>) {
+        for (id, client) in self.clients.iter_mut() {
+            if Some(*id) != except && client.name.is_some() {
+                Self::send(*id, client, message);
+            }
+        }
+    }
+
+    /// Sends a message to a joined player
+    fn send_to(&mut self, id: PlayerId, message: &ServerMessage) {
+        if let Some(client) = self.clients.get_mut(&id) {
+            if client.name.is_some() {
+                Self::send(id, client, message);
+            }
+        }
+    }
+
+    fn send(id: PlayerId, client: &mut Client, message: &ServerMessage) {
+        if let Err(e) = write_message(&mut client.stream, message) {
+            // The reading thread reports the disconnection
+            log::warn!("Failed to send message to player {}, {}", id, e);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::io::{self, ErrorKind};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Mutex;
+
+use bevy::log;
+use bevy::prelude::{Entity, Resource};
+
+use super::protocol::*;
+use crate::resources::{Action, BoardOptions, TimedAction};
+
+/// Publishes the actions played on a local board to the spectators connecting to it.
+///
+/// Inserted as a resource, the first local board is streamed, along with every action played
+/// on it. Spectators joining later receive the whole stream so far.
+#[derive(Resource)]
+pub struct SpectatorServer {
+    /// Non blocking listener, polled by [`Self::accept`]
+    listener: TcpListener,
+    spectators: Vec<TcpStream>,
+    /// Streamed board entity, with its options and the time the stream started
+    board: Option<(Entity, BoardOptions, f32)>,
+    actions: Vec<TimedAction>,
+}
+
+impl SpectatorServer {
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            spectators: Vec::new(),
+            board: None,
+            actions: Vec::new(),
+        })
+    }
+
+    /// Address spectators connect to
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Streamed board entity
+    pub fn board(&self) -> Option<Entity> {
+        self.board.as_ref().map(|(entity, ..)| *entity)
+    }
+
+    /// Starts streaming `board` at `time` seconds, `options` describing its current state
+    pub fn start(&mut self, board: Entity, options: BoardOptions, time: f32) {
+        self.broadcast(&SpectatorMessage::Board(options.clone()));
+        self.board = Some((board, options, time));
+        self.actions.clear();
+    }
+
+    /// Streams an action played at `time` seconds
+    pub fn publish(&mut self, action: Action, time: f32) {
+        let Some((_, _, started)) = self.board else {
+            return;
+        };
+        let timed = TimedAction {
+            time: time - started,
+            action,
+        };
+        self.broadcast(&SpectatorMessage::Action(timed));
+        self.actions.push(timed);
+    }
+
+    /// Accepts the pending spectators, sending them the stream so far
+    pub fn accept(&mut self) {
+        loop {
+            let mut stream = match self.listener.accept() {
+                Ok((stream, address)) => {
+                    log::info!("Spectator connected from {}", address);
+                    stream
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) => {
+                    log::error!("Failed to accept spectator, {}", e);
+                    return;
+                }
+            };
+            if let Err(e) = stream.set_nonblocking(false) {
+                log::error!("Failed to configure spectator connection, {}", e);
+                continue;
+            }
+            let board = self
+                .board
+                .iter()
+                .map(|(_, options, _)| SpectatorMessage::Board(options.clone()));
+            let actions = self.actions.iter().map(|a| SpectatorMessage::Action(*a));
+            let sent = board
+                .chain(actions)
+                .try_for_each(|message| write_message(&mut stream, &message));
+            match sent {
+                Ok(()) => self.spectators.push(stream),
+                Err(e) => log::warn!("Failed to send the game to a spectator, {}", e),
+            }
+        }
+    }
+
+    fn broadcast(&mut self, message: &SpectatorMessage) {
+        self.spectators.retain_mut(|stream| {
+            let sent = write_message(stream, message);
+            if let Err(e) = &sent {
+                log::info!("Spectator disconnected, {}", e);
+            }
+            sent.is_ok()
+        });
+    }
+}
+
+/// Connection to a [`SpectatorServer`].
+///
+/// Inserted as a resource, the streamed board is spawned when entering the running state and
+/// replays the streamed actions, without accepting any input.
+#[derive(Resource)]
+pub struct SpectatorClient {
+    stream: TcpStream,
+    receiver: Mutex<Receiver<SpectatorMessage>>,
+}
+
+impl SpectatorClient {
+    pub fn connect(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        let receiver = spawn_reader(stream.try_clone()?);
+        Ok(Self {
+            stream,
+            receiver: Mutex::new(receiver),
+        })
+    }
+
+    /// Next received message, if any
+    pub fn try_recv(&self) -> Result<SpectatorMessage, TryRecvError> {
+        match self.receiver.lock() {
+            Ok(receiver) => receiver.try_recv(),
+            Err(_) => Err(TryRecvError::Disconnected),
+        }
+    }
+}
+
+impl Drop for SpectatorClient {
+    fn drop(&mut self) {
+        // Stops the connection thread
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
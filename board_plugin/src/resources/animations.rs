@@ -0,0 +1,33 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+/// Explosion and win animation options, reveal waves being set per board by
+/// [`super::RevealAnimation`]
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+pub struct AnimationSettings {
+    /// Skips every animation, reveal waves included, showing their end at once
+    pub reduced_motion: bool,
+    /// Delay in seconds between two mines uncovered after an explosion
+    pub mine_delay: f32,
+    /// Maximum delay in seconds of the last uncovered mine
+    pub max_chain_duration: f32,
+    /// Board shake amplitude after an explosion, in tiles
+    pub shake_amplitude: f32,
+    /// Board shake duration in seconds
+    pub shake_duration: f32,
+    /// Duration in seconds of the confetti of a completed board
+    pub celebration_duration: f32,
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            reduced_motion: false,
+            mine_delay: 0.08,
+            max_chain_duration: 1.5,
+            shake_amplitude: 0.15,
+            shake_duration: 0.4,
+            celebration_duration: 2.5,
+        }
+    }
+}
@@ -3,52 +3,38 @@ use bevy::{log, prelude::*, utils::HashMap};
 
 use super::tile_map::TileMap;
 
-#[derive(Debug, Resource)]
+/// Board state, inserted on the board entity
+#[derive(Debug, Component)]
 pub struct Board {
     pub tile_map: TileMap,
+    /// World space bounds of the board
     pub bounds: Bounds2,
     pub tile_size: f32,
-    pub entity: Entity,
     pub covered_tiles: HashMap<Coordinates, Entity>,
-    pub marked_tiles: Vec<Coordinates>,
+    /// Covers of the ghost tiles mirroring the edges of a wrapping board
+    pub ghost_covers: HashMap<Coordinates, Vec<Entity>>,
 }
 
 impl Board {
-    pub fn init() -> Self {
-        Board {
-            tile_map: TileMap::empty(0, 0),
-            bounds: Bounds2 {
-                position: Vec2 { x: 0., y: 0. },
-                size: Vec2 { x: 0., y: 0. },
-            },
-            tile_size: 0.,
-            entity: Entity::from_bits(0),
-            covered_tiles: HashMap::default(),
-            marked_tiles: Vec::default(),
-        }
-    }
-
-    /// Translates a mouse position to board coordinates.
-    pub fn mouse_position(&self, window: &Window, position: Vec2) -> Option<Coordinates> {
-        // Window to world space
-        let window_size = Vec2::new(window.width(), window.height());
-        let position = position - window_size / 2.;
-
+    /// Translates a world space position to board coordinates.
+    pub fn world_to_coordinates(&self, position: Vec2) -> Option<Coordinates> {
         // Bounds check
         if !self.bounds.in_bounds(position) {
             return None;
         }
         // World space to board space
         let coordinates = position - self.bounds.position;
-        Some(Coordinates {
-            x: (coordinates.x / self.tile_size) as u16,
-            y: (coordinates.y / self.tile_size) as u16,
-        })
+        let coordinates = self
+            .tile_map
+            .topology()
+            .tile_at(coordinates, self.tile_size)?;
+        (coordinates.x < self.tile_map.width() && coordinates.y < self.tile_map.height())
+            .then_some(coordinates)
     }
 
     /// Retrieves a covered tile entity
     pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<&Entity> {
-        if self.marked_tiles.contains(coords) {
+        if self.tile_map.is_flagged_at(*coords) {
             None
         } else {
             self.covered_tiles.get(coords)
@@ -57,12 +43,71 @@ impl Board {
 
     /// We try to uncover a tile, returning the entity
     pub fn try_uncover_tile(&mut self, coords: &Coordinates) -> Option<Entity> {
-        if self.marked_tiles.contains(coords) {
-            self.unmark_tile(coords)?;
-        }
+        self.tile_map.reveal(*coords);
         self.covered_tiles.remove(coords)
     }
 
+    /// Reveals the tile at `coords` and the whole empty region around it.
+    ///
+    /// Returns the newly revealed tiles with their cover entities, ghost covers included,
+    /// starting with `coords`. Marked tiles are not revealed.
+    pub fn reveal(&mut self, coords: &Coordinates) -> Vec<(Coordinates, Entity)> {
+        if self.tile_map.is_flagged_at(*coords) {
+            return Vec::new();
+        }
+        let revealed = self.tile_map.reveal_from(*coords);
+        self.take_covers(revealed)
+    }
+
+    /// Chords the revealed tile at `coords`: if its bomb count matches the adjacent marks, stacked
+    /// marks included, every unmarked adjacent tile is revealed.
+    ///
+    /// Returns the newly revealed tiles like [`Self::reveal`]
+    pub fn chord(&mut self, coords: &Coordinates) -> Vec<(Coordinates, Entity)> {
+        let revealed = self.tile_map.chord(*coords);
+        self.take_covers(revealed)
+    }
+
+    /// Reveals the `tiles` revealed elsewhere, like on a [`crate::net::GameServer`].
+    ///
+    /// Returns the newly revealed tiles like [`Self::reveal`]
+    pub fn reveal_tiles(&mut self, tiles: Vec<Coordinates>) -> Vec<(Coordinates, Entity)> {
+        for c in &tiles {
+            self.tile_map.reveal(*c);
+        }
+        self.take_covers(tiles)
+    }
+
+    /// Removes the covers of the `revealed` tiles, ghost covers included
+    fn take_covers(&mut self, revealed: Vec<Coordinates>) -> Vec<(Coordinates, Entity)> {
+        let mut covers = Vec::with_capacity(revealed.len());
+        for c in revealed {
+            covers.extend(self.covered_tiles.remove(&c).map(|e| (c, e)));
+            let ghosts = self.ghost_covers.remove(&c).unwrap_or_default();
+            covers.extend(ghosts.into_iter().map(|e| (c, e)));
+        }
+        covers
+    }
+
+    /// Takes the covers of the unmarked mines, shown once the board exploded, without revealing
+    /// them.
+    ///
+    /// Returns the covers like [`Self::reveal`]
+    pub fn take_mine_covers(&mut self) -> Vec<(Coordinates, Entity)> {
+        let mines = self
+            .covered_tiles
+            .keys()
+            .filter(|c| self.tile_map.is_bomb_at(**c) && !self.tile_map.is_flagged_at(**c))
+            .copied()
+            .collect();
+        self.take_covers(mines)
+    }
+
+    /// Retrieves the ghost cover entities mirroring the tile at `coords`
+    pub fn ghost_covers(&self, coords: &Coordinates) -> &[Entity] {
+        self.ghost_covers.get(coords).map_or(&[], Vec::as_slice)
+    }
+
     /// We retrieve the adjacent covered tile entities of `coord`
     pub fn adjacent_covered_tiles(&self, coord: Coordinates) -> Vec<Entity> {
         self.tile_map
@@ -72,32 +117,38 @@ impl Board {
             .collect()
     }
 
-    pub fn try_toggle_mark(&mut self, coords: &Coordinates) -> Option<(Entity, bool)> {
+    /// Stacks a mark on the covered tile at `coords`, returning its cover entity and new mark
+    /// count
+    pub fn try_toggle_mark(&mut self, coords: &Coordinates) -> Option<(Entity, u8)> {
         let entity = *self.covered_tiles.get(coords)?;
-        let mark = if self.marked_tiles.contains(coords) {
-            self.unmark_tile(coords)?;
-            false
-        } else {
-            self.marked_tiles.push(*coords);
-            true
-        };
-        Some((entity, mark))
-    }
-
-    /// Removes the `coords` from `marked_tiles`
-    fn unmark_tile(&mut self, coords: &Coordinates) -> Option<Coordinates> {
-        let pos = match self.marked_tiles.iter().position(|c| c == coords) {
+        let marks = match self.tile_map.cycle_flag(*coords) {
             None => {
-                log::error!("Failed to unmark tile at {}", coords);
+                log::error!("Failed to toggle mark on tile at {}", coords);
                 return None;
             }
-            Some(pos) => pos,
+            Some(marks) => marks,
         };
-        Some(self.marked_tiles.remove(pos))
+        Some((entity, marks))
+    }
+
+    /// Sets the mark count of the covered tile at `coords`, returning its cover entity
+    pub fn set_marks(&mut self, coords: &Coordinates, marks: u8) -> Option<Entity> {
+        let entity = *self.covered_tiles.get(coords)?;
+        self.tile_map.set_flags_at(*coords, marks).then_some(entity)
+    }
+
+    /// Is the tile at `coords` marked
+    pub fn is_marked(&self, coords: &Coordinates) -> bool {
+        self.tile_map.is_flagged_at(*coords)
+    }
+
+    /// Has a mine been revealed
+    pub fn is_exploded(&self) -> bool {
+        self.tile_map.is_exploded()
     }
 
     /// Is the board complete
     pub fn is_completed(&self) -> bool {
-        self.tile_map.bomb_count() as usize == self.covered_tiles.len()
+        self.tile_map.is_completed()
     }
 }
@@ -1,5 +1,8 @@
 use bevy::prelude::*;
 use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::utils::HashMap;
+
+use super::BoardSound;
 
 /// Material of a `Sprite` with a texture and color
 #[derive(Debug, Clone)]
@@ -17,10 +20,11 @@ impl Default for SpriteMaterial {
     }
 }
 
-/// Assets for the board. Must be used as a resource.
+/// Assets for the board. Must be used as a resource, boards may override it with their own
+/// component.
 ///
-/// Use the loader for partial setup
-#[derive(Resource)]
+/// Usually loaded from a [`super::Theme`] file
+#[derive(Debug, Clone, Resource, Component)]
 pub struct BoardAssets {
     pub label: String,
     pub board_material: SpriteMaterial,
@@ -28,10 +32,58 @@ pub struct BoardAssets {
     pub covered_tile_material: SpriteMaterial,
     /// bomb neighbor text font
     pub bomb_counter_font: Handle<Font>,
-    /// bomb neighbor text colors
+    /// bomb neighbor text colors, from 1 to 8
     pub bomb_counter_colors: Vec<Color>,
+    /// Outlines the bomb counters and repeats their count as pips, readable without colors
+    pub high_contrast: bool,
     pub flag_material: SpriteMaterial,
     pub bomb_material: SpriteMaterial,
+    /// Sprite sheet replacing the tile, cover, flag and bomb materials and the bomb counters
+    pub skin: Option<TileSkin>,
+    /// Sounds played on the board events, silent if missing
+    pub sounds: HashMap<BoardSound, Handle<AudioSource>>,
+}
+
+/// Classic sprite sheet skin, every tile being drawn as a single sprite of the sheet.
+///
+/// The sheet cells follow the [`SkinTile`] order
+#[derive(Debug, Clone)]
+pub struct TileSkin {
+    pub atlas: Handle<TextureAtlas>,
+    /// Draws the bomb counters from the sheet, text counters being drawn over the empty cell
+    /// otherwise
+    pub numbers: bool,
+}
+
+/// Cells of a [`TileSkin`] sprite sheet
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SkinTile {
+    Covered,
+    Pressed,
+    /// Revealed tile with its bomb neighbor count, from 0 to 8
+    Revealed(u8),
+    Flag,
+    Mine,
+    ExplodedMine,
+    WrongFlag,
+}
+
+impl SkinTile {
+    /// Number of cells of a sheet
+    pub const COUNT: usize = 15;
+
+    /// Index of the cell in the sheet
+    pub fn index(&self) -> usize {
+        match self {
+            Self::Covered => 0,
+            Self::Pressed => 1,
+            Self::Revealed(count) => 2 + (*count).min(8) as usize,
+            Self::Flag => 11,
+            Self::Mine => 12,
+            Self::ExplodedMine => 13,
+            Self::WrongFlag => 14,
+        }
+    }
 }
 
 impl BoardAssets {
@@ -43,6 +95,9 @@ impl BoardAssets {
             Color::YELLOW,
             Color::ORANGE,
             Color::PURPLE,
+            Color::CYAN,
+            Color::PINK,
+            Color::SILVER,
         ]
     }
 
@@ -1,8 +1,20 @@
-use bevy::prelude::{Resource, Vec3};
+use std::fmt::{self, Display, Formatter};
+
+use bevy::prelude::{Component, Resource, Vec3};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 
+use super::{Neighborhood, TileMap, Topology};
+use crate::components::BoardPlayer;
+use crate::formats::{parse_layout, LayoutError};
+
+/// Generator of the seeded boards, pinned for a seed to give the same board on every platform
+/// and release, unlike `StdRng`
+pub type BoardRng = ChaCha8Rng;
+
 /// Tile size options
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TileSize {
     Fixed(f32),
     Adaptive { min: f32, max: f32 },
@@ -15,15 +27,91 @@ pub enum BoardPosition {
     Custom(Vec3),
 }
 
-/// Board generation options. Must be used as a resource
-#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+/// Reveal animation options, revealed tiles are uncovered in a wave spreading from the
+/// revealed tile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevealAnimation {
+    /// Delay in seconds between two rings of the wave
+    pub tile_delay: f32,
+    /// Maximum delay in seconds of the last uncovered tile
+    pub max_duration: f32,
+}
+
+/// Source of the board tiles
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub enum BoardSource {
+    /// Randomly placed bombs, following `map_size` and `bomb_count`
+    #[default]
+    Random,
+    /// Hand authored text layout, see [`crate::formats::parse_layout`]
+    Layout(String),
+}
+
+/// Options that can't build a board
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BoardOptionsError {
+    /// The layout source can't be parsed
+    Layout(LayoutError),
+    /// Hexagonal rows only line up across the edges of wrapping boards with an even height
+    OddWrappingHexHeight(u16),
+    /// The custom neighborhood has more than [`Neighborhood::MAX_CUSTOM_DELTAS`] deltas
+    TooManyNeighbors,
+}
+
+impl Display for BoardOptionsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Layout(e) => write!(f, "invalid board layout, {}", e),
+            Self::OddWrappingHexHeight(height) => write!(
+                f,
+                "wrapping hexagonal boards need an even height, not {}",
+                height
+            ),
+            Self::TooManyNeighbors => write!(
+                f,
+                "custom neighborhoods have at most {} deltas",
+                Neighborhood::MAX_CUSTOM_DELTAS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BoardOptionsError {}
+
+/// Board generation options.
+///
+/// Used as a resource, the boards of its players are created when entering the running state.
+/// Used as a component, a board is built on its entity.
+#[derive(Debug, Clone, Serialize, Deserialize, Resource, Component)]
 pub struct BoardOptions {
+    pub source: BoardSource,
+    /// Random generation seed, boards generated with the same seed and options are identical
+    pub seed: Option<u64>,
+    /// Players, each one playing its own board with the same layout.
+    ///
+    /// More than one player splits the screen vertically, every board getting its own
+    /// [`crate::components::BoardCamera`]
+    pub players: Vec<BoardPlayer>,
     pub map_size: (u16, u16),
+    /// Total mine weight of the board
     pub bomb_count: u16,
+    /// Maximum number of mines held by a single tile, from 1 to 3
+    pub max_mines_per_tile: u8,
+    pub topology: Topology,
+    /// Tiles counted as neighbors
+    pub neighborhood: Neighborhood,
+    /// Wraps the board at its edges, tiles on opposite edges being neighbors.
+    ///
+    /// Hexagonal boards need an even height to wrap vertically
+    pub wrapping: bool,
     pub position: BoardPosition,
     pub tile_size: TileSize,
     pub tile_padding: f32,
     pub safe_start: bool,
+    /// Adds a question mark after the flags when cycling the marks of a tile
+    pub question_marks: bool,
+    /// Animates cascading reveals, uncovered instantly if `None`
+    pub reveal_animation: Option<RevealAnimation>,
 }
 
 impl Default for TileSize {
@@ -43,15 +131,82 @@ impl Default for BoardPosition {
     }
 }
 
+impl Default for RevealAnimation {
+    fn default() -> Self {
+        Self {
+            tile_delay: 0.03,
+            max_duration: 0.5,
+        }
+    }
+}
+
 impl Default for BoardOptions {
     fn default() -> Self {
         Self {
+            source: BoardSource::Random,
+            seed: None,
+            players: vec![BoardPlayer::default()],
             map_size: (15, 15),
             bomb_count: 30,
+            max_mines_per_tile: 1,
+            topology: Topology::Square,
+            neighborhood: Neighborhood::Moore,
+            wrapping: false,
             position: Default::default(),
             tile_size: Default::default(),
             tile_padding: 0.,
             safe_start: false,
+            question_marks: false,
+            reveal_animation: None,
+        }
+    }
+}
+
+impl BoardOptions {
+    /// Generator placing the bombs, seeded with [`Self::seed`] if any
+    pub fn rng(&self) -> BoardRng {
+        match self.seed {
+            Some(seed) => BoardRng::seed_from_u64(seed),
+            None => BoardRng::from_entropy(),
+        }
+    }
+
+    /// Builds the tile map described by the options source, placing random bombs with `rng`
+    pub fn generate_tile_map(&self, rng: &mut impl Rng) -> Result<TileMap, BoardOptionsError> {
+        let layout = match &self.source {
+            BoardSource::Random => None,
+            BoardSource::Layout(text) => {
+                Some(parse_layout(text).map_err(BoardOptionsError::Layout)?)
+            }
+        };
+        if self.neighborhood.is_too_large() {
+            return Err(BoardOptionsError::TooManyNeighbors);
+        }
+        let height = layout.as_ref().map_or(self.map_size.1, TileMap::height);
+        if self.wrapping && self.topology == Topology::Hexagonal && height % 2 == 1 {
+            return Err(BoardOptionsError::OddWrappingHexHeight(height));
         }
+        let tile_map = match layout {
+            Some(tile_map) => {
+                let max_mines = tile_map.max_mines_per_tile().max(self.max_mines_per_tile);
+                tile_map
+                    .with_topology(self.topology)
+                    .with_neighborhood(self.neighborhood.clone())
+                    .with_max_mines_per_tile(max_mines)
+                    .with_wrapping(self.wrapping)
+                    .with_question_marks(self.question_marks)
+            }
+            None => {
+                let mut tile_map = TileMap::empty(self.map_size.0, self.map_size.1)
+                    .with_topology(self.topology)
+                    .with_neighborhood(self.neighborhood.clone())
+                    .with_max_mines_per_tile(self.max_mines_per_tile)
+                    .with_wrapping(self.wrapping)
+                    .with_question_marks(self.question_marks);
+                tile_map.set_bombs_with_rng(self.bomb_count, rng);
+                tile_map
+            }
+        };
+        Ok(tile_map)
     }
 }
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Classic board presets
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// 9x9 board with 10 mines
+    Beginner,
+    /// 16x16 board with 40 mines
+    Intermediate,
+    /// 30x16 board with 99 mines
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 3] = [Self::Beginner, Self::Intermediate, Self::Expert];
+
+    pub fn map_size(&self) -> (u16, u16) {
+        match self {
+            Self::Beginner => (9, 9),
+            Self::Intermediate => (16, 16),
+            Self::Expert => (30, 16),
+        }
+    }
+
+    pub fn bomb_count(&self) -> u16 {
+        match self {
+            Self::Beginner => 10,
+            Self::Intermediate => 40,
+            Self::Expert => 99,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use bevy::asset::{AssetLoader, BoxedFuture, Error, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+
+pub use fluent_bundle::FluentValue;
+pub use unic_langid::LanguageIdentifier;
+
+/// Language used when no catalog of the [`Locale`] has a message
+pub const DEFAULT_LANGUAGE: &str = "en-US";
+
+/// Messages of a language, loaded from a Fluent `assets/locales/<language>.ftl` file by the
+/// [`CatalogLoader`]
+///
+/// ```ftl
+/// board-completed = Board completed!
+/// board-flags = { $count ->
+///     [one] 1 flag
+///    *[other] { $count } flags
+/// }
+/// ```
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "9b3e2d47-6c1a-4f58-8e0b-2a7d5c9f1e34"]
+pub struct Catalog(pub Arc<FluentResource>);
+
+/// Loads [`Catalog`] assets from Fluent files
+#[derive(Debug, Default)]
+pub struct CatalogLoader;
+
+impl AssetLoader for CatalogLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let text = String::from_utf8(bytes.to_vec())?;
+            let resource = FluentResource::try_new(text).map_err(|(_, errors)| {
+                Error::msg(format!(
+                    "Invalid catalog {}, {:?}",
+                    load_context.path().display(),
+                    errors
+                ))
+            })?;
+            load_context.set_default_asset(LoadedAsset::new(Catalog(Arc::new(resource))));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+/// Language of the texts, switched at runtime by replacing the resource
+///
+/// Messages missing from the catalog of the language are looked up in its base language, like
+/// `fr` for `fr-CA`, then in the `fallbacks` and finally in the [`DEFAULT_LANGUAGE`]
+#[derive(Debug, Clone, Eq, PartialEq, Resource)]
+pub struct Locale {
+    pub language: LanguageIdentifier,
+    pub fallbacks: Vec<LanguageIdentifier>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::new(Self::default_language())
+    }
+}
+
+impl Locale {
+    pub fn new(language: LanguageIdentifier) -> Self {
+        Self {
+            language,
+            fallbacks: Vec::new(),
+        }
+    }
+
+    pub fn default_language() -> LanguageIdentifier {
+        DEFAULT_LANGUAGE
+            .parse()
+            .expect("The default language is a valid identifier")
+    }
+
+    /// Languages in the order their catalogs are looked up, without duplicates
+    pub fn languages(&self) -> Vec<LanguageIdentifier> {
+        let base = |language: &LanguageIdentifier| {
+            LanguageIdentifier::from_parts(language.language, None, None, &[])
+        };
+        let mut languages: Vec<LanguageIdentifier> = Vec::new();
+        let chain = [self.language.clone(), base(&self.language)]
+            .into_iter()
+            .chain(self.fallbacks.iter().flat_map(|f| [f.clone(), base(f)]))
+            .chain([Self::default_language()]);
+        for language in chain {
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+        }
+        languages
+    }
+
+    /// Path of the catalog of a language, relative to the asset folder
+    pub fn catalog_path(language: &LanguageIdentifier) -> String {
+        format!("locales/{}.ftl", language)
+    }
+}
+
+/// Formats the messages of the [`Locale`] catalogs, see [`Localization::format`]
+///
+/// The resource is changed once the catalogs are loaded or reloaded, the localized texts having
+/// to be rebuilt then
+#[derive(Default, Resource)]
+pub struct Localization {
+    pub(crate) catalogs: Vec<(LanguageIdentifier, Handle<Catalog>)>,
+    bundles: Vec<FluentBundle<Arc<FluentResource>>>,
+}
+
+impl Localization {
+    /// Builds the message bundles of the loaded catalogs, in the lookup order
+    pub(crate) fn build_bundles(&mut self, catalogs: &Assets<Catalog>) {
+        self.bundles = self
+            .catalogs
+            .iter()
+            .filter_map(|(language, handle)| {
+                let catalog = catalogs.get(handle)?;
+                let mut bundle = FluentBundle::new_concurrent(vec![language.clone()]);
+                // Unicode isolation marks are drawn as boxes by the game font
+                bundle.set_use_isolating(false);
+                if let Err(errors) = bundle.add_resource(catalog.0.clone()) {
+                    bevy::log::warn!("Conflicting {} messages, {:?}", language, errors);
+                }
+                Some(bundle)
+            })
+            .collect();
+    }
+
+    /// Whether the existing catalogs of every language have been loaded
+    pub fn is_loaded(&self) -> bool {
+        !self.bundles.is_empty() && self.bundles.len() == self.catalogs.len()
+    }
+
+    /// Message without arguments, see [`Self::format`]
+    pub fn message(&self, id: &str) -> String {
+        self.format(id, [])
+    }
+
+    /// Message `id` of the first catalog having it, formatted with the given arguments.
+    ///
+    /// Unknown messages are returned as their id
+    pub fn format<'a>(
+        &self,
+        id: &str,
+        args: impl IntoIterator<Item = (&'a str, FluentValue<'a>)>,
+    ) -> String {
+        let args: FluentArgs = args.into_iter().collect();
+        for bundle in &self.bundles {
+            let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, Some(&args), &mut errors);
+            if !errors.is_empty() {
+                bevy::log::warn!("Failed to format the {} message, {:?}", id, errors);
+            }
+            return text.into_owned();
+        }
+        id.to_string()
+    }
+}
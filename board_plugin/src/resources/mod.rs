@@ -1,9 +1,30 @@
+pub(crate) mod packed_layer;
 pub(crate) mod tile;
 pub(crate) mod tile_map;
+pub use animations::*;
 pub use board::*;
 pub use board_assets::*;
 pub use board_options::*;
+pub use difficulty::Difficulty;
+pub use locale::*;
+pub use neighborhood::Neighborhood;
+pub use palette::*;
+pub use replay::*;
+pub use sounds::*;
+pub use theme::*;
+pub use tile::Tile;
+pub use tile_map::TileMap;
+pub use topology::*;
 
+mod animations;
 mod board;
 mod board_assets;
 mod board_options;
+mod difficulty;
+mod locale;
+mod neighborhood;
+mod palette;
+mod replay;
+mod sounds;
+mod theme;
+mod topology;
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use super::{TileMap, Topology};
+
+/// Neighborhood rule, defining which tiles count as neighbors for bomb counts, flood fill and
+/// chording
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Neighborhood {
+    /// Every adjacent tile: 8 square or 6 hexagonal neighbors
+    #[default]
+    Moore,
+    /// Edge adjacent tiles only: 4 square or 6 hexagonal neighbors
+    VonNeumann,
+    /// Tiles a chess knight's move away
+    Knight,
+    /// Every tile at a distance of 2 or less
+    Radius2,
+    /// Arbitrary delta coordinates, `(0, 0)` and duplicates are ignored.
+    ///
+    /// Only the first [`Self::MAX_CUSTOM_DELTAS`] deltas are kept
+    Custom(Vec<(i8, i8)>),
+}
+
+impl Neighborhood {
+    /// Most custom deltas, for the neighbor mine counts of the fullest tiles to fit a byte
+    pub const MAX_CUSTOM_DELTAS: usize = (u8::MAX / TileMap::MAX_MINES_PER_TILE) as usize;
+
+    /// Delta coordinates of the neighbors of a tile for a given `topology`.
+    ///
+    /// Deltas are expressed like [`Topology::neighbor_deltas`], so axial on hexagonal grids.
+    pub fn deltas(&self, topology: Topology) -> Vec<(i8, i8)> {
+        match (self, topology) {
+            (Self::Moore, _) | (Self::VonNeumann, Topology::Hexagonal) => {
+                topology.neighbor_deltas().to_vec()
+            }
+            (Self::VonNeumann, Topology::Square) => VON_NEUMANN_COORDINATES.to_vec(),
+            (Self::Knight, _) => KNIGHT_COORDINATES.to_vec(),
+            (Self::Radius2, Topology::Square) => Self::disk(|dx, dy| dx.abs().max(dy.abs())),
+            (Self::Radius2, Topology::Hexagonal) => {
+                Self::disk(|dq, dr| dq.abs().max(dr.abs()).max((dq + dr).abs()))
+            }
+            (Self::Custom(deltas), _) => {
+                let mut unique = Self::unique(deltas);
+                unique.truncate(Self::MAX_CUSTOM_DELTAS);
+                unique
+            }
+        }
+    }
+
+    /// Whether a custom neighborhood has more than [`Self::MAX_CUSTOM_DELTAS`] deltas
+    pub fn is_too_large(&self) -> bool {
+        match self {
+            Self::Custom(deltas) => Self::unique(deltas).len() > Self::MAX_CUSTOM_DELTAS,
+            _ => false,
+        }
+    }
+
+    /// Deltas without `(0, 0)` and duplicates, in their order
+    fn unique(deltas: &[(i8, i8)]) -> Vec<(i8, i8)> {
+        let mut unique = Vec::with_capacity(deltas.len());
+        for delta in deltas {
+            if *delta != (0, 0) && !unique.contains(delta) {
+                unique.push(*delta);
+            }
+        }
+        unique
+    }
+
+    /// Every delta at a `distance` of 1 or 2
+    fn disk(distance: impl Fn(i8, i8) -> i8) -> Vec<(i8, i8)> {
+        (-2..=2)
+            .flat_map(|dy| (-2..=2).map(move |dx| (dx, dy)))
+            .filter(|(dx, dy)| (1..=2).contains(&distance(*dx, *dy)))
+            .collect()
+    }
+}
+
+/// Delta coordinates for the 4 edge adjacent square neighbors
+const VON_NEUMANN_COORDINATES: [(i8, i8); 4] = [
+    // Bottom
+    (0, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top
+    (0, 1),
+];
+
+/// Delta coordinates for the 8 knight's move neighbors
+const KNIGHT_COORDINATES: [(i8, i8); 8] = [
+    (-1, -2),
+    (1, -2),
+    (-2, -1),
+    (2, -1),
+    (-2, 1),
+    (2, 1),
+    (-1, 2),
+    (1, 2),
+];
@@ -0,0 +1,108 @@
+/// Flat layer storing one small value per tile, packed in 64 bit words
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PackedLayer<const BITS: usize> {
+    words: Vec<u64>,
+    len: usize,
+}
+
+/// Layer storing one boolean per tile
+pub type BitLayer = PackedLayer<1>;
+
+impl<const BITS: usize> PackedLayer<BITS> {
+    const PER_WORD: usize = u64::BITS as usize / BITS;
+    const MASK: u64 = (1 << BITS) - 1;
+    /// Largest storable value
+    pub const MAX: u8 = Self::MASK as u8;
+
+    /// Creates a layer of `len` zeroed values
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(Self::PER_WORD)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Retrieves the value at `index`, out of range values are zero
+    #[inline]
+    pub fn get(&self, index: usize) -> u8 {
+        if index >= self.len {
+            return 0;
+        }
+        let shift = (index % Self::PER_WORD) * BITS;
+        ((self.words[index / Self::PER_WORD] >> shift) & Self::MASK) as u8
+    }
+
+    /// Sets the value at `index`, truncated to `BITS` bits, returning the previous value
+    #[inline]
+    pub fn set(&mut self, index: usize, value: u8) -> u8 {
+        let previous = self.get(index);
+        let word = &mut self.words[index / Self::PER_WORD];
+        let shift = (index % Self::PER_WORD) * BITS;
+        *word = (*word & !(Self::MASK << shift)) | ((value as u64 & Self::MASK) << shift);
+        previous
+    }
+
+    /// Zeroes every value of the layer
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+}
+
+impl BitLayer {
+    /// Is the bit at `index` set
+    #[inline]
+    pub fn is_set(&self, index: usize) -> bool {
+        self.get(index) != 0
+    }
+
+    /// Sets the bit at `index`, returning its previous value
+    #[inline]
+    pub fn set_bit(&mut self, index: usize, value: bool) -> bool {
+        self.set(index, value as u8) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_bits_across_words() {
+        let mut layer = BitLayer::new(130);
+        assert_eq!(layer.words.len(), 3);
+        for index in [0, 63, 64, 127, 128, 129] {
+            assert!(!layer.set_bit(index, true));
+        }
+        assert!(layer.set_bit(63, true));
+        let set: Vec<usize> = (0..130).filter(|i| layer.is_set(*i)).collect();
+        assert_eq!(set, [0, 63, 64, 127, 128, 129]);
+        assert!(layer.set_bit(64, false));
+        assert!(!layer.is_set(64) && layer.is_set(63) && layer.is_set(127));
+        // Out of range bits read as unset
+        assert!(!layer.is_set(130));
+    }
+
+    #[test]
+    fn packs_two_bit_values_across_words() {
+        let mut layer = PackedLayer::<2>::new(65);
+        assert_eq!((layer.words.len(), PackedLayer::<2>::MAX), (3, 3));
+        for (index, value) in [(0, 1), (31, 3), (32, 2), (63, 3), (64, 1)] {
+            assert_eq!(layer.set(index, value), 0);
+        }
+        // Neighbor values are left untouched at the word boundaries
+        assert_eq!(layer.set(31, 1), 3);
+        let values: Vec<u8> = [0, 1, 30, 31, 32, 33, 63, 64]
+            .map(|i| layer.get(i))
+            .to_vec();
+        assert_eq!(values, [1, 0, 0, 1, 2, 0, 3, 1]);
+        // Values are truncated to 2 bits
+        layer.set(1, 6);
+        assert_eq!((layer.get(0), layer.get(1), layer.get(2)), (1, 2, 0));
+        layer.clear();
+        assert!((0..65).all(|i| layer.get(i) == 0));
+    }
+}
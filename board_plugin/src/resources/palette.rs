@@ -0,0 +1,51 @@
+use bevy::prelude::{Color, Resource};
+use serde::{Deserialize, Serialize};
+
+/// Bomb counter color presets
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Palette {
+    /// Colors of the theme
+    #[default]
+    Theme,
+    /// Avoids the green and red confusion of green weak vision
+    Deuteranopia,
+    /// Avoids the green and red confusion of red weak vision, reds looking darker
+    Protanopia,
+    /// Avoids the blue and yellow confusion of blue weak vision
+    Tritanopia,
+}
+
+impl Palette {
+    pub const ALL: [Self; 4] = [
+        Self::Theme,
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::Tritanopia,
+    ];
+
+    /// Colors of the counters from 1 to 8, `None` keeping the theme colors
+    pub fn colors(&self) -> Option<Vec<Color>> {
+        let hex: [&str; 8] = match self {
+            Self::Theme => return None,
+            Self::Deuteranopia => [
+                "56B4E9", "E69F00", "F0E442", "FFFFFF", "CC79A7", "A6D8FF", "FFC48A", "B0B0B0",
+            ],
+            Self::Protanopia => [
+                "6CB6FF", "FFB000", "FFF36B", "FFFFFF", "B48CFF", "9EE6FF", "FFD9A0", "B0B0B0",
+            ],
+            Self::Tritanopia => [
+                "FF6E6E", "3EC7C7", "FFFFFF", "FF9EC4", "A0E8E0", "FFC0C0", "7FD8FF", "B0B0B0",
+            ],
+        };
+        Some(hex.iter().filter_map(|h| Color::hex(h).ok()).collect())
+    }
+}
+
+/// Bomb counter palette and high contrast cues overriding the theme ones, see
+/// [`super::BoardTheme`]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Resource)]
+pub struct BoardPalette {
+    pub palette: Palette,
+    /// Outlines the counters and adds count pips, see [`super::BoardAssets::high_contrast`]
+    pub high_contrast: bool,
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use super::TileMap;
+use crate::components::Coordinates;
+
+/// Player action on a board tile
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    /// Reveals a tile and the empty region around it
+    Reveal(Coordinates),
+    /// Stacks a mark on a covered tile
+    Mark(Coordinates),
+    /// Reveals the unmarked neighbors of a revealed tile
+    Chord(Coordinates),
+}
+
+impl Action {
+    /// Coordinates of the tile targeted by the action
+    pub fn coordinates(&self) -> Coordinates {
+        match self {
+            Self::Reveal(c) | Self::Mark(c) | Self::Chord(c) => *c,
+        }
+    }
+
+    /// Plays the action on `tile_map`, returning the newly revealed tiles.
+    ///
+    /// Marked tiles can't be revealed, like on a board.
+    pub fn apply(&self, tile_map: &mut TileMap) -> Vec<Coordinates> {
+        match *self {
+            Self::Reveal(c) if tile_map.is_flagged_at(c) => Vec::new(),
+            Self::Reveal(c) => tile_map.reveal_from(c),
+            Self::Mark(c) => {
+                tile_map.cycle_flag(c);
+                Vec::new()
+            }
+            Self::Chord(c) => tile_map.chord(c),
+        }
+    }
+}
+
+/// Action played `time` seconds after the start of a game
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimedAction {
+    pub time: f32,
+    pub action: Action,
+}
+
+/// Board with the actions played on it
+#[derive(Debug, Clone)]
+pub struct Replay {
+    /// Board before the first action
+    pub tile_map: TileMap,
+    pub actions: Vec<TimedAction>,
+}
+
+impl Replay {
+    /// Plays every action on a copy of the board, returning the final board
+    pub fn play(&self) -> TileMap {
+        let mut tile_map = self.tile_map.clone();
+        for timed in &self.actions {
+            timed.action.apply(&mut tile_map);
+        }
+        tile_map
+    }
+}
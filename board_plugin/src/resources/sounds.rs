@@ -0,0 +1,74 @@
+use bevy::prelude::Resource;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Sound effects played on board events
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum BoardSound {
+    /// A single tile revealed
+    Reveal,
+    /// Several tiles revealed at once
+    Cascade,
+    Flag,
+    Unflag,
+    Chord,
+    Explosion,
+    /// Board completed
+    Win,
+}
+
+impl BoardSound {
+    pub const ALL: [Self; 7] = [
+        Self::Reveal,
+        Self::Cascade,
+        Self::Flag,
+        Self::Unflag,
+        Self::Chord,
+        Self::Explosion,
+        Self::Win,
+    ];
+
+    /// Sound file used by themes not overriding it
+    pub fn default_path(&self) -> &'static str {
+        match self {
+            Self::Reveal => "sounds/reveal.wav",
+            Self::Cascade => "sounds/cascade.wav",
+            Self::Flag => "sounds/flag.wav",
+            Self::Unflag => "sounds/unflag.wav",
+            Self::Chord => "sounds/chord.wav",
+            Self::Explosion => "sounds/explosion.wav",
+            Self::Win => "sounds/win.wav",
+        }
+    }
+}
+
+/// Volume of the board sounds, the sound files being part of the
+/// [`super::BoardAssets`]
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+pub struct AudioSettings {
+    pub muted: bool,
+    /// Volume of every sound, from 0 to 1
+    pub master_volume: f32,
+    /// Volume of each sound, from 0 to 1, full if missing
+    pub volumes: HashMap<BoardSound, f32>,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            master_volume: 1.,
+            volumes: HashMap::default(),
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Playback volume of `sound`, 0 when muted
+    pub fn volume(&self, sound: BoardSound) -> f32 {
+        match self.muted {
+            true => 0.,
+            false => self.master_volume * self.volumes.get(&sound).copied().unwrap_or(1.),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use bevy::asset::{AssetLoader, AssetPath, BoxedFuture, Error, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+use super::{BoardAssets, BoardSound, SkinTile, SpriteMaterial, TileSkin};
+
+/// Board look, loaded from a `.theme.ron` or `.theme.toml` file by the [`ThemeLoader`].
+///
+/// ```ron
+/// (
+///     label: "Default",
+///     board: (color: "#FFFFFF"),
+///     tile: (color: "#404040"),
+///     cover: (color: "#808080"),
+///     flag: (texture: "sprites/flag.png"),
+///     bomb: (texture: "sprites/bomb.png"),
+///     font: "fonts/pixeled.ttf",
+///     counter_colors: [
+///         "#FFFFFF", "#00FF00", "#FFFF00", "#FFA500",
+///         "#800080", "#00FFFF", "#FFC0CB", "#C0C0C0",
+///     ],
+///     padding: 1.0,
+/// )
+/// ```
+///
+/// Colors are hex strings, textures and fonts are paths relative to the asset folder.
+///
+/// An optional `skin` draws the tiles from a sprite sheet of [`super::SkinTile::COUNT`] cells
+/// instead, `(texture: "sprites/classic.png", tile_size: (32., 32.), columns: 15, rows: 1)`
+/// with an optional `padding` between the cells.
+///
+/// Sound files of the [`super::BoardSound`]s may be overridden, as in
+/// `sounds: {Explosion: "sounds/boom.wav"}`, the default ones being used otherwise.
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "5f0c7a52-3b2e-4f0e-9a43-4c8e1f6d2b91"]
+pub struct Theme {
+    pub assets: BoardAssets,
+    pub tile_padding: f32,
+}
+
+/// Theme applied to the boards, replacing the [`BoardAssets`] resource and the
+/// [`super::BoardOptions`] tile padding.
+///
+/// The boards are rebuilt in their current state whenever the theme changes, or its file is
+/// reloaded.
+#[derive(Debug, Clone, Resource)]
+pub struct BoardTheme {
+    pub theme: Handle<Theme>,
+    /// Tile padding used instead of the theme one
+    pub tile_padding: Option<f32>,
+}
+
+impl BoardTheme {
+    pub fn new(theme: Handle<Theme>) -> Self {
+        Self {
+            theme,
+            tile_padding: None,
+        }
+    }
+}
+
+/// Loads [`Theme`] assets from RON or TOML files
+#[derive(Debug, Default)]
+pub struct ThemeLoader;
+
+/// Theme file contents
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    label: String,
+    #[serde(default)]
+    board: MaterialFile,
+    #[serde(default)]
+    tile: MaterialFile,
+    #[serde(default)]
+    cover: MaterialFile,
+    #[serde(default)]
+    flag: MaterialFile,
+    #[serde(default)]
+    bomb: MaterialFile,
+    font: String,
+    /// Bomb counter colors, the default ones if empty
+    #[serde(default)]
+    counter_colors: Vec<String>,
+    #[serde(default)]
+    padding: f32,
+    skin: Option<SkinFile>,
+    #[serde(default)]
+    sounds: HashMap<BoardSound, String>,
+}
+
+/// Sprite sheet grid in a theme file
+#[derive(Debug, Deserialize)]
+struct SkinFile {
+    texture: String,
+    tile_size: Vec2,
+    columns: usize,
+    rows: usize,
+    #[serde(default)]
+    padding: Vec2,
+}
+
+/// Sprite material in a theme file, white and untextured by default
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MaterialFile {
+    color: Option<String>,
+    texture: Option<String>,
+}
+
+impl AssetLoader for ThemeLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let text = std::str::from_utf8(bytes)?;
+            let file: ThemeFile = match load_context.path().extension() {
+                Some(extension) if extension == "toml" => toml::from_str(text)?,
+                _ => ron::Options::default()
+                    .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+                    .from_str(text)?,
+            };
+            let mut dependencies = Vec::new();
+            let mut material = |material: &MaterialFile| -> Result<SpriteMaterial, Error> {
+                let texture = match &material.texture {
+                    Some(path) => {
+                        let path = AssetPath::from(path.as_str()).to_owned();
+                        dependencies.push(path.clone());
+                        load_context.get_handle(path)
+                    }
+                    None => DEFAULT_IMAGE_HANDLE.typed(),
+                };
+                Ok(SpriteMaterial {
+                    color: material
+                        .color
+                        .as_deref()
+                        .map_or(Ok(Color::WHITE), parse_color)?,
+                    texture,
+                })
+            };
+            let board_material = material(&file.board)?;
+            let tile_material = material(&file.tile)?;
+            let covered_tile_material = material(&file.cover)?;
+            let flag_material = material(&file.flag)?;
+            let bomb_material = material(&file.bomb)?;
+            let bomb_counter_colors = match file.counter_colors.is_empty() {
+                true => BoardAssets::default_colors(),
+                false => file
+                    .counter_colors
+                    .iter()
+                    .map(|c| parse_color(c))
+                    .collect::<Result<_, _>>()?,
+            };
+            let skin = match file.skin {
+                Some(skin) => {
+                    let texture = AssetPath::from(skin.texture.as_str()).to_owned();
+                    dependencies.push(texture.clone());
+                    let atlas = TextureAtlas::from_grid(
+                        load_context.get_handle(texture),
+                        skin.tile_size,
+                        skin.columns,
+                        skin.rows,
+                        Some(skin.padding),
+                        None,
+                    );
+                    if atlas.len() < SkinTile::COUNT {
+                        return Err(Error::msg(format!(
+                            "The skin has {} cells instead of {}",
+                            atlas.len(),
+                            SkinTile::COUNT
+                        )));
+                    }
+                    let atlas = load_context.set_labeled_asset("skin", LoadedAsset::new(atlas));
+                    Some(TileSkin {
+                        atlas,
+                        numbers: true,
+                    })
+                }
+                None => None,
+            };
+            let sounds = BoardSound::ALL
+                .into_iter()
+                .map(|sound| {
+                    let path = match file.sounds.get(&sound) {
+                        Some(path) => path.as_str(),
+                        None => sound.default_path(),
+                    };
+                    let path = AssetPath::from(path).to_owned();
+                    dependencies.push(path.clone());
+                    (sound, load_context.get_handle(path))
+                })
+                .collect();
+            let font = AssetPath::from(file.font.as_str()).to_owned();
+            dependencies.push(font.clone());
+            let theme = Theme {
+                assets: BoardAssets {
+                    label: file.label,
+                    board_material,
+                    tile_material,
+                    covered_tile_material,
+                    bomb_counter_font: load_context.get_handle(font),
+                    bomb_counter_colors,
+                    high_contrast: false,
+                    flag_material,
+                    bomb_material,
+                    skin,
+                    sounds,
+                },
+                tile_padding: file.padding,
+            };
+            load_context.set_default_asset(LoadedAsset::new(theme).with_dependencies(dependencies));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron", "theme.toml"]
+    }
+}
+
+fn parse_color(hex: &str) -> Result<Color, Error> {
+    Color::hex(hex).map_err(|e| Error::msg(format!("Invalid color {:?}, {}", hex, e)))
+}
@@ -3,14 +3,15 @@ use colored::Colorize;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Tile {
-    Bomb,
+    /// Tile holding one or more mines
+    Bomb(u8),
     BombNeighbor(u8),
     Empty,
 }
 
 impl Tile {
     pub const fn is_bomb(&self) -> bool {
-        matches!(self, Self::Bomb)
+        matches!(self, Self::Bomb(_))
     }
 
     #[cfg(feature = "debug")]
@@ -18,7 +19,8 @@ impl Tile {
         format!(
             "{}",
             match self {
-                Tile::Bomb => "*".bright_red(),
+                Tile::Bomb(1) => "*".bright_red(),
+                Tile::Bomb(v) => v.to_string().bright_red().bold(),
                 Tile::BombNeighbor(v) => match v {
                     1 => "1".cyan(),
                     2 => "2".green(),
@@ -1,31 +1,111 @@
+use std::ops::Index;
+
 use rand::{thread_rng, Rng};
 
 use crate::components::Coordinates;
+use crate::resources::packed_layer::{BitLayer, PackedLayer};
 use crate::resources::tile::Tile;
-use std::ops::{Deref, DerefMut};
+use crate::resources::{Neighborhood, Topology};
 
+/// Board tile storage.
+///
+/// Tiles are stored row by row in flat layers: packed mine and flag count layers, a bit-packed
+/// revealed layer and a byte layer caching the neighbor bomb counts.
+///
+/// A tile can hold up to [`Self::MAX_MINES_PER_TILE`] mines, neighbor counts summing them.
 #[derive(Debug, Clone)]
 pub struct TileMap {
     bomb_count: u16,
     height: u16,
     width: u16,
-    map: Vec<Vec<Tile>>,
+    topology: Topology,
+    neighborhood: Neighborhood,
+    /// Neighbor delta coordinates matching the topology and neighborhood
+    deltas: Vec<(i8, i8)>,
+    wrapping: bool,
+    max_mines_per_tile: u8,
+    /// Adds a question mark to the flag cycle of the tiles
+    question_marks: bool,
+    mines: PackedLayer<2>,
+    revealed: BitLayer,
+    flagged: PackedLayer<2>,
+    questioned: BitLayer,
+    neighbors: Vec<u8>,
+    mined_count: usize,
+    revealed_count: usize,
+    flagged_count: usize,
+    /// Revealed mined tiles, the map being exploded with any of them
+    exploded_count: usize,
 }
 
 impl TileMap {
+    /// Maximum number of mines a single tile can hold
+    pub const MAX_MINES_PER_TILE: u8 = PackedLayer::<2>::MAX;
+
     /// Generates an empty map
     pub fn empty(width: u16, height: u16) -> Self {
-        let map = (0..height)
-            .map(|_| (0..width).map(|_| Tile::Empty).collect())
-            .collect();
+        let len = width as usize * height as usize;
         Self {
             bomb_count: 0,
             height,
             width,
-            map,
+            topology: Topology::Square,
+            neighborhood: Neighborhood::Moore,
+            deltas: Neighborhood::Moore.deltas(Topology::Square),
+            wrapping: false,
+            max_mines_per_tile: 1,
+            question_marks: false,
+            mines: PackedLayer::new(len),
+            revealed: BitLayer::new(len),
+            flagged: PackedLayer::new(len),
+            questioned: BitLayer::new(len),
+            neighbors: vec![0; len],
+            mined_count: 0,
+            revealed_count: 0,
+            flagged_count: 0,
+            exploded_count: 0,
         }
     }
 
+    /// Sets the map grid topology
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self.deltas = self.neighborhood.deltas(topology);
+        self.refresh_bomb_counts();
+        self
+    }
+
+    /// Sets the neighborhood rule used for bomb counts and flood fill
+    pub fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.deltas = neighborhood.deltas(self.topology);
+        self.neighborhood = neighborhood;
+        self.refresh_bomb_counts();
+        self
+    }
+
+    /// Makes the map wrap at its edges, opposite edges becoming neighbors
+    pub fn with_wrapping(mut self, wrapping: bool) -> Self {
+        self.wrapping = wrapping;
+        self.refresh_bomb_counts();
+        self
+    }
+
+    /// Allows tiles to hold up to `max` mines, clamped to [`Self::MAX_MINES_PER_TILE`]
+    pub fn with_max_mines_per_tile(mut self, max: u8) -> Self {
+        self.max_mines_per_tile = max.clamp(1, Self::MAX_MINES_PER_TILE);
+        self
+    }
+
+    /// Cycles the marks of the tiles through a question mark after their flags, see
+    /// [`Self::cycle_flag`]
+    pub fn with_question_marks(mut self, question_marks: bool) -> Self {
+        self.question_marks = question_marks;
+        if !question_marks {
+            self.questioned.clear();
+        }
+        self
+    }
+
     #[cfg(feature = "debug")]
     pub fn console_output(&self) -> String {
         let mut buffer = format!(
@@ -34,9 +114,10 @@ impl TileMap {
         );
         let line: String = (0..=(self.width + 1)).map(|_| '-').collect();
         buffer = format!("{}{}\n", buffer, line);
-        for line in self.map.iter() {
+        for y in 0..self.height {
             buffer = format!("{}|", buffer);
-            for tile in line.iter() {
+            for x in 0..self.width {
+                let tile = self.tile_at(Coordinates { x, y });
                 buffer = format!("{}{}", buffer, tile.console_output());
             }
             buffer = format!("{}|\n", buffer);
@@ -52,107 +133,450 @@ impl TileMap {
         self.height
     }
 
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn neighborhood(&self) -> &Neighborhood {
+        &self.neighborhood
+    }
+
+    /// Farthest distance between a tile and its neighbors along an axis
+    pub fn reach(&self) -> u16 {
+        self.deltas
+            .iter()
+            .map(|(dx, dy)| dx.unsigned_abs().max(dy.unsigned_abs()) as u16)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn is_wrapping(&self) -> bool {
+        self.wrapping
+    }
+
+    pub fn max_mines_per_tile(&self) -> u8 {
+        self.max_mines_per_tile
+    }
+
+    /// Total mine weight of the map
     pub fn bomb_count(&self) -> u16 {
         self.bomb_count
     }
 
-    pub fn safe_square_at(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> {
-        SQUARE_COORDINATES
-            .iter()
-            .copied()
-            .map(move |tuple| coordinates + tuple)
+    /// Number of tiles holding at least a mine
+    pub fn mined_count(&self) -> usize {
+        self.mined_count
     }
 
-    pub fn is_bomb_at(&self, coordinates: Coordinates) -> bool {
+    /// Number of revealed tiles
+    pub fn revealed_count(&self) -> usize {
+        self.revealed_count
+    }
+
+    /// Number of placed flags, stacked flags included
+    pub fn flagged_count(&self) -> usize {
+        self.flagged_count
+    }
+
+    /// Flat index of `coordinates`, if in range
+    #[inline]
+    fn index(&self, coordinates: Coordinates) -> Option<usize> {
         if coordinates.x >= self.width || coordinates.y >= self.height {
-            return false;
+            return None;
+        }
+        Some(coordinates.y as usize * self.width as usize + coordinates.x as usize)
+    }
+
+    /// Coordinates matching a flat index
+    #[inline]
+    fn coordinates(&self, index: usize) -> Coordinates {
+        Coordinates {
+            x: (index % self.width as usize) as u16,
+            y: (index / self.width as usize) as u16,
         }
-        self.map[coordinates.y as usize][coordinates.x as usize].is_bomb()
+    }
+
+    /// Iterates over the in range neighbors of `coordinates`.
+    ///
+    /// On wrapping maps, out of range neighbors wrap to the opposite edge. Maps too small to hold
+    /// the whole neighborhood yield each neighbor once and never `coordinates` itself.
+    pub fn safe_square_at(
+        &self,
+        coordinates: Coordinates,
+    ) -> impl Iterator<Item = Coordinates> + '_ {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let (topology, wrapping) = (self.topology, self.wrapping);
+        let deltas = &self.deltas;
+        // Wrapped neighbors can overlap if the neighborhood is as wide as the map, hexagonal
+        // deltas being axial they shift up to half their row delta further horizontally
+        let reach = self.reach() as i32;
+        let x_reach = match topology {
+            Topology::Square => reach,
+            Topology::Hexagonal => reach + (reach + 1) / 2,
+        };
+        let overlapping = wrapping && (width <= 2 * x_reach || height <= 2 * reach);
+        let wrap = move |delta: &(i8, i8)| {
+            let (x, y) = topology.offset(coordinates, *delta);
+            if wrapping {
+                Some((x.rem_euclid(width), y.rem_euclid(height)))
+            } else {
+                ((0..width).contains(&x) && (0..height).contains(&y)).then_some((x, y))
+            }
+        };
+        deltas.iter().enumerate().filter_map(move |(i, delta)| {
+            let (x, y) = wrap(delta)?;
+            if overlapping
+                && ((x, y) == (coordinates.x as i32, coordinates.y as i32)
+                    || deltas[..i].iter().any(|d| wrap(d) == Some((x, y))))
+            {
+                return None;
+            }
+            Some(Coordinates {
+                x: x as u16,
+                y: y as u16,
+            })
+        })
+    }
+
+    /// Retrieves the tile at `coordinates`, out of range tiles are empty
+    pub fn tile_at(&self, coordinates: Coordinates) -> Tile {
+        match self.index(coordinates) {
+            Some(i) if self.mines.get(i) > 0 => Tile::Bomb(self.mines.get(i)),
+            Some(i) if self.neighbors[i] > 0 => Tile::BombNeighbor(self.neighbors[i]),
+            _ => Tile::Empty,
+        }
+    }
+
+    /// Iterates over every tile, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinates, Tile)> + '_ {
+        (0..self.mines.len()).map(|i| {
+            let coordinates = self.coordinates(i);
+            (coordinates, self.tile_at(coordinates))
+        })
+    }
+
+    /// Tiles row by row, as the map used to dereference to.
+    ///
+    /// The rows are a copy: the map can't be edited through them anymore, tiles being changed
+    /// with the setters like [`Self::set_mines_at`] instead. Single tiles are read by indexing
+    /// the map with their coordinates, `tile_map[coordinates]` replacing `tile_map[y][x]`.
+    pub fn rows(&self) -> Vec<Vec<Tile>> {
+        (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| self.tile_at(Coordinates { x, y }))
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn is_bomb_at(&self, coordinates: Coordinates) -> bool {
+        self.mines_at(coordinates) > 0
+    }
+
+    /// Number of mines held by the tile at `coordinates`
+    pub fn mines_at(&self, coordinates: Coordinates) -> u8 {
+        self.index(coordinates).map_or(0, |i| self.mines.get(i))
     }
 
     pub fn bomb_count_at(&self, coordinates: Coordinates) -> u8 {
-        if self.is_bomb_at(coordinates) {
-            return 0;
+        match self.index(coordinates) {
+            Some(i) if self.mines.get(i) == 0 => self.neighbors[i],
+            _ => 0,
         }
-        let res = self
-            .safe_square_at(coordinates)
-            .filter(|coord| self.is_bomb_at(*coord))
-            .count();
-        res as u8
     }
 
+    pub fn is_revealed_at(&self, coordinates: Coordinates) -> bool {
+        self.index(coordinates)
+            .is_some_and(|i| self.revealed.is_set(i))
+    }
+
+    pub fn is_flagged_at(&self, coordinates: Coordinates) -> bool {
+        self.flags_at(coordinates) > 0
+    }
+
+    /// Number of flags stacked on the tile at `coordinates`
+    pub fn flags_at(&self, coordinates: Coordinates) -> u8 {
+        self.index(coordinates).map_or(0, |i| self.flagged.get(i))
+    }
+
+    /// Is the tile at `coordinates` marked with a question mark, which doesn't count as a flag
+    pub fn is_questioned_at(&self, coordinates: Coordinates) -> bool {
+        self.index(coordinates)
+            .is_some_and(|i| self.questioned.is_set(i))
+    }
+
+    /// Distributes a total mine weight of `bomb_count` over the map, a tile receiving up to
+    /// `max_mines_per_tile` mines
     pub fn set_bombs(&mut self, bomb_count: u16) {
-        self.bomb_count = bomb_count;
-        let mut remaining_bombs = bomb_count;
-        let mut rng = thread_rng();
+        self.set_bombs_with_rng(bomb_count, &mut thread_rng());
+    }
+
+    /// Distributes bombs like [`Self::set_bombs`] using `rng`, seeded generators giving
+    /// reproducible boards
+    pub fn set_bombs_with_rng(&mut self, bomb_count: u16, rng: &mut impl Rng) {
+        let capacity = self.mines.len() * self.max_mines_per_tile as usize;
+        self.bomb_count = bomb_count.min(capacity.try_into().unwrap_or(u16::MAX));
+        self.mines.clear();
+        self.mined_count = 0;
+        self.exploded_count = 0;
+        let mut remaining_bombs = self.bomb_count;
 
         // place bombs
         while remaining_bombs > 0 {
-            let (x, y) = (
-                rng.gen_range(0..self.width) as usize,
-                rng.gen_range(0..self.height) as usize,
-            );
-            if let Tile::Empty = self[y][x] {
-                self[y][x] = Tile::Bomb;
+            // Sampled as `u32`, `usize` samples depending on the platform
+            let i = rng.gen_range(0..self.mines.len() as u32) as usize;
+            let mines = self.mines.get(i);
+            if mines < self.max_mines_per_tile {
+                self.mines.set(i, mines + 1);
+                if mines == 0 {
+                    self.mined_count += 1;
+                    self.exploded_count += self.revealed.is_set(i) as usize;
+                }
                 remaining_bombs -= 1;
             }
         }
 
-        // place bomb neighbors
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let coords = Coordinates { x, y };
-                if self.is_bomb_at(coords) {
-                    continue;
+        self.refresh_bomb_counts();
+    }
+
+    /// Places `mines` mines on the tile at `coordinates`, up to `max_mines_per_tile`.
+    ///
+    /// Neighbor bomb counts are not updated, see [`Self::refresh_bomb_counts`]
+    pub fn set_mines_at(&mut self, coordinates: Coordinates, mines: u8) {
+        let Some(i) = self.index(coordinates) else {
+            return;
+        };
+        let mines = mines.min(self.max_mines_per_tile);
+        let previous = self.mines.set(i, mines);
+        self.bomb_count = self.bomb_count + mines as u16 - previous as u16;
+        let revealed = self.revealed.is_set(i) as usize;
+        match (previous, mines) {
+            (0, 1..) => {
+                self.mined_count += 1;
+                self.exploded_count += revealed;
+            }
+            (1.., 0) => {
+                self.mined_count -= 1;
+                self.exploded_count -= revealed;
+            }
+            _ => (),
+        }
+    }
+
+    /// Recomputes the cached neighbor bomb counts
+    pub fn refresh_bomb_counts(&mut self) {
+        if self.mined_count == 0 {
+            self.neighbors.iter_mut().for_each(|n| *n = 0);
+            return;
+        }
+        for i in 0..self.neighbors.len() {
+            let coordinates = self.coordinates(i);
+            self.neighbors[i] = self
+                .safe_square_at(coordinates)
+                .map(|c| self.mines_at(c))
+                .sum();
+        }
+    }
+
+    /// Stacks a flag on the tile at `coordinates`, clearing its flags once `max_mines_per_tile`
+    /// flags are stacked. Returns the new flag count.
+    ///
+    /// With question marks, the cleared flags are replaced by a question mark first, see
+    /// [`Self::is_questioned_at`].
+    ///
+    /// Revealed and out of range tiles can't be flagged.
+    pub fn cycle_flag(&mut self, coordinates: Coordinates) -> Option<u8> {
+        let i = self.index(coordinates)?;
+        if self.revealed.is_set(i) {
+            return None;
+        }
+        if self.questioned.set_bit(i, false) {
+            return Some(0);
+        }
+        let previous = self.flagged.get(i);
+        let flags = (previous + 1) % (self.max_mines_per_tile + 1);
+        self.questioned
+            .set_bit(i, self.question_marks && flags == 0);
+        self.flagged.set(i, flags);
+        self.flagged_count = self.flagged_count + flags as usize - previous as usize;
+        Some(flags)
+    }
+
+    /// Stacks `flags` flags on the covered tile at `coordinates`, up to `max_mines_per_tile`.
+    ///
+    /// Returns `false` if the tile is out of range or revealed.
+    pub fn set_flags_at(&mut self, coordinates: Coordinates, flags: u8) -> bool {
+        let Some(i) = self.index(coordinates) else {
+            return false;
+        };
+        if self.revealed.is_set(i) {
+            return false;
+        }
+        let flags = flags.min(self.max_mines_per_tile);
+        self.questioned.set_bit(i, false);
+        let previous = self.flagged.set(i, flags);
+        self.flagged_count = self.flagged_count + flags as usize - previous as usize;
+        true
+    }
+
+    /// Reveals a single tile, clearing its flag.
+    ///
+    /// Returns `false` if the tile is out of range or already revealed.
+    pub fn reveal(&mut self, coordinates: Coordinates) -> bool {
+        match self.index(coordinates) {
+            Some(i) => self.reveal_index(i),
+            None => false,
+        }
+    }
+
+    fn reveal_index(&mut self, i: usize) -> bool {
+        if self.revealed.set_bit(i, true) {
+            return false;
+        }
+        self.revealed_count += 1;
+        if self.mines.get(i) > 0 {
+            self.exploded_count += 1;
+        }
+        self.flagged_count -= self.flagged.set(i, 0) as usize;
+        self.questioned.set_bit(i, false);
+        true
+    }
+
+    /// Reveals the tile at `coordinates` and flood fills the empty region around it.
+    ///
+    /// Returns every newly revealed tile, starting with `coordinates`.
+    pub fn reveal_from(&mut self, coordinates: Coordinates) -> Vec<Coordinates> {
+        let mut revealed = Vec::new();
+        let Some(start) = self.index(coordinates) else {
+            return revealed;
+        };
+        if !self.reveal_index(start) {
+            return revealed;
+        }
+        revealed.push(coordinates);
+        if self.mines.get(start) > 0 {
+            return revealed;
+        }
+        let mut stack = vec![start];
+        let mut neighbors = Vec::with_capacity(self.deltas.len());
+        while let Some(i) = stack.pop() {
+            if self.neighbors[i] > 0 {
+                continue;
+            }
+            neighbors.extend(self.safe_square_at(self.coordinates(i)));
+            for neighbor in neighbors.drain(..) {
+                let n = neighbor.y as usize * self.width as usize + neighbor.x as usize;
+                if self.reveal_index(n) {
+                    revealed.push(neighbor);
+                    stack.push(n);
                 }
-                let num = self.bomb_count_at(coords);
-                if num == 0 {
+            }
+        }
+        revealed
+    }
+
+    /// Chords the revealed tile at `coordinates`: if its bomb count matches the adjacent flags,
+    /// stacked flags included, every unflagged adjacent tile is revealed like
+    /// [`Self::reveal_from`].
+    ///
+    /// Returns every newly revealed tile.
+    pub fn chord(&mut self, coordinates: Coordinates) -> Vec<Coordinates> {
+        let count = self.bomb_count_at(coordinates);
+        if count == 0 || !self.is_revealed_at(coordinates) {
+            return Vec::new();
+        }
+        let neighbors: Vec<Coordinates> = self.safe_square_at(coordinates).collect();
+        let flags: u8 = neighbors.iter().map(|c| self.flags_at(*c)).sum();
+        if flags != count {
+            return Vec::new();
+        }
+        let mut revealed = Vec::new();
+        for c in neighbors {
+            if !self.is_flagged_at(c) {
+                revealed.extend(self.reveal_from(c));
+            }
+        }
+        revealed
+    }
+
+    /// Bechtel's Board Benchmark Value (3BV): the minimum number of reveals needed to clear the
+    /// board without chording, each empty region counting once along with every numbered tile
+    /// out of those regions
+    pub fn three_bv(&self) -> usize {
+        let mut visited = BitLayer::new(self.mines.len());
+        let mut three_bv = 0;
+        let mut stack = Vec::new();
+        // Empty regions and their numbered borders
+        for start in 0..self.mines.len() {
+            if self.mines.get(start) > 0 || self.neighbors[start] > 0 || visited.is_set(start) {
+                continue;
+            }
+            three_bv += 1;
+            visited.set_bit(start, true);
+            stack.push(start);
+            while let Some(i) = stack.pop() {
+                if self.neighbors[i] > 0 {
                     continue;
                 }
-                let tile = &mut self[y as usize][x as usize];
-                *tile = Tile::BombNeighbor(num);
+                for neighbor in self.safe_square_at(self.coordinates(i)) {
+                    let n = neighbor.y as usize * self.width as usize + neighbor.x as usize;
+                    if self.mines.get(n) == 0 && !visited.set_bit(n, true) {
+                        stack.push(n);
+                    }
+                }
             }
         }
+        // Isolated numbered tiles
+        three_bv
+            + (0..self.mines.len())
+                .filter(|&i| self.mines.get(i) == 0 && !visited.is_set(i))
+                .count()
     }
-}
 
-impl Deref for TileMap {
-    type Target = Vec<Vec<Tile>>;
+    /// Has a mine been revealed
+    pub fn is_exploded(&self) -> bool {
+        self.exploded_count > 0
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.map
+    /// Is every safe tile revealed
+    pub fn is_completed(&self) -> bool {
+        self.mines.len() - self.revealed_count == self.mined_count
     }
 }
 
-impl DerefMut for TileMap {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.map
+/// Tiles of every value, for the map indexing to hand out references to its computed tiles
+const TILES: [[Tile; 256]; 2] = {
+    let mut tiles = [[Tile::Empty; 256]; 2];
+    let mut value = 1;
+    while value < 256 {
+        tiles[0][value] = Tile::Bomb(value as u8);
+        tiles[1][value] = Tile::BombNeighbor(value as u8);
+        value += 1;
     }
-}
+    tiles
+};
 
-/// Delta coordinates for all 8 square neighbors
-/// *--------*-------*-------*
-/// | -1, 1  | 0, 1  | 1, 1  |
-/// |--------|-------|-------|
-/// | -1, 0  | tile  | 1, 0  |
-/// |--------|-------|-------|
-/// | -1, -1 | 0, -1 | 1, -1 |
-/// *--------*-------*-------*
-const SQUARE_COORDINATES: [(i8, i8); 8] = [
-    // Bottom left
-    (-1, -1),
-    // Bottom
-    (0, -1),
-    // Bottom right
-    (1, -1),
-    // Left
-    (-1, 0),
-    // Right
-    (1, 0),
-    // Top Left
-    (-1, 1),
-    // Top
-    (0, 1),
-    // Top right
-    (1, 1),
-];
+/// Read-only tile access, like [`TileMap::tile_at`].
+///
+/// # Panics
+///
+/// Panics if `coordinates` are out of the map, like the former row indexing
+impl Index<Coordinates> for TileMap {
+    type Output = Tile;
+
+    fn index(&self, coordinates: Coordinates) -> &Tile {
+        assert!(
+            coordinates.x < self.width && coordinates.y < self.height,
+            "{} is out of the {}x{} map",
+            coordinates,
+            self.width,
+            self.height
+        );
+        match self.tile_at(coordinates) {
+            Tile::Bomb(mines) => &TILES[0][mines as usize],
+            Tile::BombNeighbor(count) => &TILES[1][count as usize],
+            Tile::Empty => &TILES[0][0],
+        }
+    }
+}
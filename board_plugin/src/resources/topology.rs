@@ -0,0 +1,228 @@
+use crate::components::Coordinates;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::{Deserialize, Serialize};
+
+/// Board grid topology
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Topology {
+    /// Square tiles with 8 neighbors
+    #[default]
+    Square,
+    /// Pointy top hexagonal tiles with 6 neighbors, odd rows are shifted right by half a tile
+    Hexagonal,
+}
+
+/// Height of a pointy top hexagon relative to its width
+const HEX_HEIGHT_RATIO: f32 = 1.154_700_5; // 2 / sqrt(3)
+
+impl Topology {
+    /// Delta coordinates of the direct neighbors of a tile.
+    ///
+    /// Square deltas are applied on the tile coordinates, hexagonal deltas are axial.
+    pub const fn neighbor_deltas(&self) -> &'static [(i8, i8)] {
+        match self {
+            Self::Square => &SQUARE_COORDINATES,
+            Self::Hexagonal => &HEX_COORDINATES,
+        }
+    }
+
+    /// Applies a neighbor `delta` to `coordinates`, the result may be out of the map
+    pub fn offset(&self, coordinates: Coordinates, (dx, dy): (i8, i8)) -> (i32, i32) {
+        let (x, y) = (coordinates.x as i32, coordinates.y as i32);
+        match self {
+            Self::Square => (x + dx as i32, y + dy as i32),
+            Self::Hexagonal => {
+                // Offset to axial coordinates, add the delta and back
+                let q = x - (y - (y & 1)) / 2 + dx as i32;
+                let r = y + dy as i32;
+                (q + (r - (r & 1)) / 2, r)
+            }
+        }
+    }
+
+    /// Number of neighbor steps between two tiles, which may be out of the map
+    pub fn distance(&self, (x1, y1): (i32, i32), (x2, y2): (i32, i32)) -> u16 {
+        let distance = match self {
+            Self::Square => (x1 - x2).abs().max((y1 - y2).abs()),
+            Self::Hexagonal => {
+                // Offset to axial coordinates, rows being shifted every other row
+                let axial = |x: i32, y: i32| x - (y - (y & 1)) / 2;
+                let (dq, dr) = (axial(x1, y1) - axial(x2, y2), y1 - y2);
+                (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+            }
+        };
+        distance as u16
+    }
+
+    /// Size of a tile sprite for a given tile `size`
+    pub fn tile_extent(&self, size: f32) -> Vec2 {
+        match self {
+            Self::Square => Vec2::splat(size),
+            Self::Hexagonal => Vec2::new(size, size * HEX_HEIGHT_RATIO),
+        }
+    }
+
+    /// Size of a `width` x `height` board for a given tile `size`
+    pub fn board_size(&self, (width, height): (u16, u16), size: f32) -> Vec2 {
+        let (width, height) = (width as f32, height as f32);
+        match self {
+            Self::Square => Vec2::new(width * size, height * size),
+            Self::Hexagonal => {
+                let extent = self.tile_extent(size);
+                let shift = if height > 1. { 0.5 } else { 0. };
+                Vec2::new(
+                    (width + shift) * size,
+                    (height - 1.) * extent.y * 0.75 + extent.y,
+                )
+            }
+        }
+    }
+
+    /// Translation between a tile and its copy on a wrapped `width` x `height` board
+    pub fn wrap_period(&self, (width, height): (u16, u16), size: f32) -> Vec2 {
+        let extent = self.tile_extent(size);
+        match self {
+            Self::Square => Vec2::new(width as f32, height as f32) * size,
+            Self::Hexagonal => Vec2::new(width as f32 * size, height as f32 * extent.y * 0.75),
+        }
+    }
+
+    /// Center of the tile at `coordinates` in board space
+    pub fn tile_center(&self, coordinates: Coordinates, size: f32) -> Vec2 {
+        let (x, y) = (coordinates.x as f32, coordinates.y as f32);
+        let extent = self.tile_extent(size);
+        match self {
+            Self::Square => Vec2::new(x * size, y * size) + extent / 2.,
+            Self::Hexagonal => {
+                let shift = (coordinates.y % 2) as f32 * 0.5;
+                Vec2::new((x + shift) * size, y * extent.y * 0.75) + extent / 2.
+            }
+        }
+    }
+
+    /// Translates a board space `position` to tile coordinates, the result may be out of the
+    /// map
+    pub fn tile_at(&self, position: Vec2, size: f32) -> Option<Coordinates> {
+        let extent = self.tile_extent(size);
+        let (x, y) = match self {
+            Self::Square => (
+                (position.x / size).floor() as i32,
+                (position.y / size).floor() as i32,
+            ),
+            Self::Hexagonal => {
+                // Fractional axial coordinates relative to the first tile center
+                let position = position - extent / 2.;
+                let radius = extent.y / 2.;
+                let q = (3f32.sqrt() / 3. * position.x - position.y / 3.) / radius;
+                let r = (2. / 3. * position.y) / radius;
+                let (q, r) = Self::axial_round(q, r);
+                (q + (r - (r & 1)) / 2, r)
+            }
+        };
+        (x >= 0 && y >= 0).then_some(Coordinates {
+            x: x as u16,
+            y: y as u16,
+        })
+    }
+
+    /// Rounds fractional axial coordinates to the nearest hexagon
+    fn axial_round(q: f32, r: f32) -> (i32, i32) {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        (rq as i32, rr as i32)
+    }
+
+    /// Generates a white tile mask texture, `None` for topologies using plain sprites
+    pub fn tile_mask(&self) -> Option<Image> {
+        match self {
+            Self::Square => None,
+            Self::Hexagonal => Some(Self::hexagon_image(64)),
+        }
+    }
+
+    /// Generates a white pointy top hexagon image with a transparent background
+    fn hexagon_image(width: u32) -> Image {
+        let height = (width as f32 * HEX_HEIGHT_RATIO).round() as u32;
+        let (half_width, half_height) = (width as f32 / 2., height as f32 / 2.);
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let dx = ((x as f32 + 0.5) - half_width).abs();
+                let dy = ((y as f32 + 0.5) - half_height).abs();
+                // Inside the vertical edges and below the slanted edges
+                let inside = dx <= half_width && dy <= half_height - dx / 3f32.sqrt();
+                let alpha = if inside { 255 } else { 0 };
+                data.extend_from_slice(&[255, 255, 255, alpha]);
+            }
+        }
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+}
+
+/// Delta coordinates for all 8 square neighbors
+/// *--------*-------*-------*
+/// | -1, 1  | 0, 1  | 1, 1  |
+/// |--------|-------|-------|
+/// | -1, 0  | tile  | 1, 0  |
+/// |--------|-------|-------|
+/// | -1, -1 | 0, -1 | 1, -1 |
+/// *--------*-------*-------*
+const SQUARE_COORDINATES: [(i8, i8); 8] = [
+    // Bottom left
+    (-1, -1),
+    // Bottom
+    (0, -1),
+    // Bottom right
+    (1, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top Left
+    (-1, 1),
+    // Top
+    (0, 1),
+    // Top right
+    (1, 1),
+];
+
+/// Axial delta coordinates for all 6 hexagonal neighbors
+///      / \     / \
+///    /     \ /     \
+///   | -1, 1 | 0, 1  |
+///  / \     / \     / \
+/// | -1, 0 | tile  | 1, 0  |
+///  \ /     \ /     \ /
+///   | 0, -1 | 1, -1 |
+///    \     / \     /
+///      \ /     \ /
+const HEX_COORDINATES: [(i8, i8); 6] = [
+    // Bottom left
+    (0, -1),
+    // Bottom right
+    (1, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top left
+    (-1, 1),
+    // Top right
+    (0, 1),
+];
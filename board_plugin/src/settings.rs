@@ -0,0 +1,117 @@
+//! Player settings, stored as a RON file and applied to the boards on startup.
+//!
+//! ```no_run
+//! use board_plugin::resources::BoardOptions;
+//! use board_plugin::settings::Settings;
+//!
+//! let settings = Settings::load("settings.ron").unwrap();
+//! let options = settings.board_options(BoardOptions::default());
+//! settings.save("settings.ron").unwrap();
+//! ```
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use bevy::prelude::AssetServer;
+use ron::extensions::Extensions;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::components::{BoardInput, KeyBindings};
+use crate::resources::{AnimationSettings, AudioSettings, BoardOptions, BoardTheme, TileSize};
+
+/// Theme file used by default
+pub const DEFAULT_THEME: &str = "themes/default.theme.ron";
+
+/// Settings of the player, missing fields of the file keeping their default value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Theme file, relative to the asset folder
+    pub theme: String,
+    pub tile_size: TileSize,
+    /// Tile padding used instead of the theme one
+    pub tile_padding: Option<f32>,
+    pub safe_start: bool,
+    /// Adds a question mark to the marks of the tiles
+    pub question_marks: bool,
+    /// Uncovers cascading reveals in a wave
+    pub reveal_animation: bool,
+    pub audio: AudioSettings,
+    pub animations: AnimationSettings,
+    /// Bindings of the keyboard cursors
+    pub key_bindings: KeyBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: DEFAULT_THEME.to_string(),
+            tile_size: TileSize::default(),
+            tile_padding: None,
+            safe_start: true,
+            question_marks: false,
+            reveal_animation: true,
+            audio: AudioSettings::default(),
+            animations: AnimationSettings::default(),
+            key_bindings: KeyBindings::arrows(),
+        }
+    }
+}
+
+impl Settings {
+    /// Loads the settings at `path`, the default ones if the file doesn't exist yet
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => ron::Options::default()
+                .with_default_extension(Extensions::IMPLICIT_SOME)
+                .from_str(&text)
+                .map_err(|e| io::Error::new(ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Saves the settings at `path`, creating its directory if needed
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let config = PrettyConfig::default().extensions(Extensions::IMPLICIT_SOME);
+        let text = ron::ser::to_string_pretty(self, config)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+        std::fs::write(path, text)
+    }
+
+    /// Options with the board settings applied, keeping the layout options of `base`.
+    ///
+    /// Keyboard players use the [`Self::key_bindings`]
+    pub fn board_options(&self, base: BoardOptions) -> BoardOptions {
+        let reveal_animation = match self.reveal_animation {
+            true => Some(base.reveal_animation.unwrap_or_default()),
+            false => None,
+        };
+        let mut players = base.players;
+        for player in &mut players {
+            if let BoardInput::Keyboard(bindings) = &mut player.input {
+                *bindings = self.key_bindings.clone();
+            }
+        }
+        BoardOptions {
+            players,
+            tile_size: self.tile_size.clone(),
+            safe_start: self.safe_start,
+            question_marks: self.question_marks,
+            reveal_animation,
+            ..base
+        }
+    }
+
+    /// Board theme loading the theme file
+    pub fn board_theme(&self, asset_server: &AssetServer) -> BoardTheme {
+        BoardTheme {
+            theme: asset_server.load(self.theme.as_str()),
+            tile_padding: self.tile_padding,
+        }
+    }
+}
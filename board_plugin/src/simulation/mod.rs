@@ -0,0 +1,131 @@
+//! Headless game simulation, running strategies on boards without any window, renderer or ECS.
+//!
+//! ```no_run
+//! use board_plugin::resources::BoardOptions;
+//! use board_plugin::simulation::{Simulation, SimpleSolver};
+//!
+//! let options = BoardOptions {
+//!     map_size: (30, 16),
+//!     bomb_count: 99,
+//!     seed: Some(42),
+//!     ..Default::default()
+//! };
+//! let report = Simulation::new(options, 1000)
+//!     .run(&mut SimpleSolver::default())
+//!     .unwrap();
+//! println!("{}", report.to_json());
+//! ```
+mod report;
+mod strategy;
+
+pub use report::*;
+pub use strategy::*;
+
+use std::time::{Duration, Instant};
+
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::resources::{BoardOptions, BoardOptionsError, BoardRng, Tile};
+
+/// Runs a number of games with consecutive seeds
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    /// Board generation options, `seed` being the seed of the first game
+    pub options: BoardOptions,
+    pub games: usize,
+    /// Actions after which a game is abandoned
+    pub max_actions: usize,
+}
+
+/// End of a simulated game
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Outcome {
+    Won,
+    Lost,
+    /// The strategy gave up or ran out of actions
+    Abandoned,
+}
+
+/// Result of a single simulated game
+#[derive(Debug, Clone)]
+pub struct GameResult {
+    pub seed: u64,
+    pub outcome: Outcome,
+    /// Number of actions played
+    pub actions: usize,
+    /// Time spent playing the game
+    pub duration: Duration,
+    /// 3BV of the board
+    pub three_bv: usize,
+}
+
+impl Simulation {
+    pub fn new(options: BoardOptions, games: usize) -> Self {
+        Self {
+            options,
+            games,
+            max_actions: 100_000,
+        }
+    }
+
+    /// Plays every game with `strategy` and aggregates the results, failing if the options can't
+    /// build a board
+    pub fn run(&self, strategy: &mut impl Strategy) -> Result<SimulationReport, BoardOptionsError> {
+        let start = Instant::now();
+        let results: Vec<GameResult> = (0..self.games)
+            .map(|game| self.play(game, strategy))
+            .collect::<Result<_, _>>()?;
+        Ok(SimulationReport::new(&results, start.elapsed()))
+    }
+
+    /// Plays the `game`-th game with `strategy`
+    pub fn play(
+        &self,
+        game: usize,
+        strategy: &mut impl Strategy,
+    ) -> Result<GameResult, BoardOptionsError> {
+        let start = Instant::now();
+        let seed = self
+            .options
+            .seed
+            .unwrap_or_default()
+            .wrapping_add(game as u64);
+        let mut tile_map = self
+            .options
+            .generate_tile_map(&mut BoardRng::seed_from_u64(seed))?;
+        let three_bv = tile_map.three_bv();
+        if self.options.safe_start && tile_map.revealed_count() == 0 {
+            let safe_start = tile_map.iter().find(|(_, tile)| *tile == Tile::Empty);
+            if let Some((coordinates, _)) = safe_start {
+                tile_map.reveal_from(coordinates);
+            }
+        }
+        strategy.new_game(seed);
+
+        let mut actions = 0;
+        let outcome = loop {
+            if tile_map.is_completed() {
+                break Outcome::Won;
+            }
+            if actions >= self.max_actions {
+                break Outcome::Abandoned;
+            }
+            let Some(action) = strategy.next_action(&BoardView::new(&tile_map)) else {
+                break Outcome::Abandoned;
+            };
+            actions += 1;
+            let revealed = action.apply(&mut tile_map);
+            if revealed.iter().any(|c| tile_map.is_bomb_at(*c)) {
+                break Outcome::Lost;
+            }
+        };
+        Ok(GameResult {
+            seed,
+            outcome,
+            actions,
+            duration: start.elapsed(),
+            three_bv,
+        })
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::{GameResult, Outcome};
+
+/// 3BV statistics of the simulated boards
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ThreeBvStats {
+    pub average: f64,
+    pub min: usize,
+    pub max: usize,
+    /// Average 3BV per action of the won games
+    pub average_efficiency: Option<f64>,
+}
+
+/// Aggregated results of a simulation
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SimulationReport {
+    pub games: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub abandoned: usize,
+    pub win_rate: f64,
+    /// Average number of actions played before losing
+    pub average_actions_to_loss: Option<f64>,
+    /// Average time in seconds spent before losing
+    pub average_seconds_to_loss: Option<f64>,
+    pub three_bv: ThreeBvStats,
+    pub games_per_second: f64,
+}
+
+impl SimulationReport {
+    pub fn new(results: &[GameResult], elapsed: Duration) -> Self {
+        let count = |outcome| results.iter().filter(|r| r.outcome == outcome).count();
+        let average = |values: Vec<f64>| {
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        };
+        let losses = results.iter().filter(|r| r.outcome == Outcome::Lost);
+        let wins = results.iter().filter(|r| r.outcome == Outcome::Won);
+        Self {
+            games: results.len(),
+            wins: count(Outcome::Won),
+            losses: count(Outcome::Lost),
+            abandoned: count(Outcome::Abandoned),
+            win_rate: average(
+                results
+                    .iter()
+                    .map(|r| (r.outcome == Outcome::Won) as u8 as f64)
+                    .collect(),
+            )
+            .unwrap_or_default(),
+            average_actions_to_loss: average(losses.clone().map(|r| r.actions as f64).collect()),
+            average_seconds_to_loss: average(losses.map(|r| r.duration.as_secs_f64()).collect()),
+            three_bv: ThreeBvStats {
+                average: average(results.iter().map(|r| r.three_bv as f64).collect())
+                    .unwrap_or_default(),
+                min: results.iter().map(|r| r.three_bv).min().unwrap_or_default(),
+                max: results.iter().map(|r| r.three_bv).max().unwrap_or_default(),
+                average_efficiency: average(
+                    wins.map(|r| r.three_bv as f64 / r.actions.max(1) as f64)
+                        .collect(),
+                ),
+            },
+            games_per_second: results.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        }
+    }
+
+    /// Pretty printed JSON report
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Failed to serialize simulation report")
+    }
+}
@@ -0,0 +1,165 @@
+use rand::seq::IteratorRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::components::Coordinates;
+use crate::resources::{Action, TileMap};
+
+/// Player visible state of a tile
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TileView {
+    /// Covered tile with its stacked flags
+    Covered { flags: u8 },
+    /// Revealed tile with its neighbor bomb count
+    Revealed { bomb_count: u8 },
+}
+
+/// Player visible state of a board, hiding the content of covered tiles
+#[derive(Debug, Copy, Clone)]
+pub struct BoardView<'a> {
+    tile_map: &'a TileMap,
+}
+
+impl<'a> BoardView<'a> {
+    pub fn new(tile_map: &'a TileMap) -> Self {
+        Self { tile_map }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.tile_map.width()
+    }
+
+    pub fn height(&self) -> u16 {
+        self.tile_map.height()
+    }
+
+    /// Total mine weight of the board
+    pub fn bomb_count(&self) -> u16 {
+        self.tile_map.bomb_count()
+    }
+
+    /// Visible state of the tile at `coordinates`, `None` if out of range
+    pub fn tile_at(&self, coordinates: Coordinates) -> Option<TileView> {
+        if coordinates.x >= self.width() || coordinates.y >= self.height() {
+            return None;
+        }
+        Some(if self.tile_map.is_revealed_at(coordinates) {
+            TileView::Revealed {
+                bomb_count: self.tile_map.bomb_count_at(coordinates),
+            }
+        } else {
+            TileView::Covered {
+                flags: self.tile_map.flags_at(coordinates),
+            }
+        })
+    }
+
+    /// Every tile with its visible state, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinates, TileView)> + 'a {
+        let view = *self;
+        (0..self.height())
+            .flat_map(move |y| (0..view.width()).map(move |x| Coordinates { x, y }))
+            .filter_map(move |c| view.tile_at(c).map(|tile| (c, tile)))
+    }
+
+    /// Neighbors of the tile at `coordinates`
+    pub fn neighbors(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> + 'a {
+        self.tile_map.safe_square_at(coordinates)
+    }
+}
+
+/// Bot playing simulated games
+pub trait Strategy {
+    /// Called before every game with the board seed
+    fn new_game(&mut self, _seed: u64) {}
+
+    /// Chooses the next action from the visible `board`, `None` giving up the game
+    fn next_action(&mut self, board: &BoardView) -> Option<Action>;
+}
+
+/// Reveals random unflagged covered tiles
+#[derive(Debug, Clone)]
+pub struct RandomStrategy {
+    rng: ChaCha8Rng,
+}
+
+impl Default for RandomStrategy {
+    fn default() -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(0),
+        }
+    }
+}
+
+impl Strategy for RandomStrategy {
+    fn new_game(&mut self, seed: u64) {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+    }
+
+    fn next_action(&mut self, board: &BoardView) -> Option<Action> {
+        board
+            .iter()
+            .filter(|(_, tile)| *tile == TileView::Covered { flags: 0 })
+            .choose(&mut self.rng)
+            .map(|(c, _)| Action::Reveal(c))
+    }
+}
+
+/// Single point solver: flags the covered neighbors of tiles missing as many flags, reveals
+/// the covered neighbors of fully flagged tiles and guesses randomly when stuck.
+///
+/// Assumes single mine tiles.
+#[derive(Debug, Clone, Default)]
+pub struct SimpleSolver {
+    guesser: RandomStrategy,
+    /// Deduced actions of the last board scan
+    pending: Vec<Action>,
+}
+
+impl Strategy for SimpleSolver {
+    fn new_game(&mut self, seed: u64) {
+        self.guesser.new_game(seed);
+        self.pending.clear();
+    }
+
+    fn next_action(&mut self, board: &BoardView) -> Option<Action> {
+        // Deductions stay valid, skipping the tiles handled meanwhile
+        let next_pending = |pending: &mut Vec<Action>| {
+            std::iter::from_fn(|| pending.pop()).find(|action| {
+                board.tile_at(action.coordinates()) == Some(TileView::Covered { flags: 0 })
+            })
+        };
+        if let Some(action) = next_pending(&mut self.pending) {
+            return Some(action);
+        }
+        self.deduce(board);
+        next_pending(&mut self.pending).or_else(|| self.guesser.next_action(board))
+    }
+}
+
+impl SimpleSolver {
+    /// Scans the board for every single point deduction
+    fn deduce(&mut self, board: &BoardView) {
+        for (coordinates, tile) in board.iter() {
+            let TileView::Revealed { bomb_count } = tile else {
+                continue;
+            };
+            let (mut flags, mut covered) = (0, Vec::new());
+            for neighbor in board.neighbors(coordinates) {
+                match board.tile_at(neighbor) {
+                    Some(TileView::Covered { flags: 0 }) => covered.push(neighbor),
+                    Some(TileView::Covered { flags: f }) => flags += f,
+                    _ => (),
+                }
+            }
+            if covered.is_empty() {
+                continue;
+            }
+            if flags == bomb_count {
+                self.pending.extend(covered.into_iter().map(Action::Reveal));
+            } else if (bomb_count - flags.min(bomb_count)) as usize == covered.len() {
+                self.pending.extend(covered.into_iter().map(Action::Mark));
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use bevy::a11y::accesskit::{Live, NodeBuilder, Role};
+use bevy::a11y::{AccessibilityNode, Focus};
+use bevy::{log, prelude::*};
+
+use crate::components::{BoardAnnouncer, BoardCursor, BoardPlayer, Coordinates};
+use crate::events::{BoardCompletedEvent, BombExplosionEvent, DescribeNeighborhoodEvent};
+use crate::resources::{Board, Localization, Tile, TileMap};
+
+/// Accessibility nodes of the board tiles
+type TileNodes<'w, 's> = Query<'w, 's, (Entity, &'static Parent, &'static Coordinates)>;
+
+/// Exposes the changed boards to assistive technologies, as a grid of tiles labeled with their
+/// coordinates and state, in the current language
+pub fn label_tiles(
+    mut commands: Commands,
+    boards: Query<(Entity, Ref<Board>, Option<&BoardPlayer>)>,
+    tiles: TileNodes,
+    localization: Res<Localization>,
+) {
+    for (entity, board, player) in boards.iter() {
+        if !board.is_changed() && !localization.is_changed() {
+            continue;
+        }
+        let tile_map = &board.tile_map;
+        let name = match player {
+            Some(player) => localization.format(
+                "board-label-player",
+                [("player", player.name.as_str().into())],
+            ),
+            None => localization.message("board-label"),
+        };
+        let mut node = NodeBuilder::new(Role::Grid);
+        node.set_name(localization.format(
+            "board-grid",
+            [
+                ("board", name.into()),
+                ("width", tile_map.width().into()),
+                ("height", tile_map.height().into()),
+                ("mines", tile_map.bomb_count().into()),
+                ("flags", tile_map.flagged_count().into()),
+            ],
+        ));
+        node.set_table_row_count(tile_map.height() as usize);
+        node.set_table_column_count(tile_map.width() as usize);
+        commands.entity(entity).insert(AccessibilityNode(node));
+        for (tile, _, coordinates) in tiles.iter().filter(|(_, p, _)| p.get() == entity) {
+            let mut node = NodeBuilder::new(Role::Cell);
+            node.set_name(tile_label(&localization, tile_map, *coordinates));
+            // Rows are read from the top
+            node.set_table_cell_row_index((tile_map.height() - 1 - coordinates.y) as usize);
+            node.set_table_cell_column_index(coordinates.x as usize);
+            commands.entity(tile).insert(AccessibilityNode(node));
+        }
+    }
+}
+
+/// Moves the accessibility focus to the tile under a moved board cursor, so that its label is
+/// announced
+pub fn focus_cursor_tiles(
+    cursors: Query<(&Parent, Ref<BoardCursor>)>,
+    tiles: TileNodes,
+    mut focus: ResMut<Focus>,
+) {
+    for (board, cursor) in cursors.iter() {
+        if !cursor.is_changed() {
+            continue;
+        }
+        let tile = tiles.iter().find(|(_, parent, coordinates)| {
+            parent.get() == board.get() && **coordinates == cursor.0
+        });
+        if let Some((tile, ..)) = tile {
+            **focus = Some(tile);
+        }
+    }
+}
+
+/// Announces the explosions, the completed boards and the requested neighborhood descriptions
+pub fn announce_board_events(
+    boards: Query<&Board>,
+    localization: Res<Localization>,
+    mut announcers: Query<(&Parent, &mut BoardAnnouncer)>,
+    mut describe_evr: EventReader<DescribeNeighborhoodEvent>,
+    (mut explosion_evr, mut completed_evr): (
+        EventReader<BombExplosionEvent>,
+        EventReader<BoardCompletedEvent>,
+    ),
+) {
+    let explosions = explosion_evr
+        .iter()
+        .map(|e| (e.board, localization.message("announce-explosion")));
+    let completions = completed_evr
+        .iter()
+        .map(|e| (e.board, localization.message("announce-completed")));
+    let descriptions = describe_evr.iter().filter_map(|e| {
+        let board = boards.get(e.board).ok()?;
+        Some((
+            e.board,
+            describe_neighborhood(&localization, &board.tile_map, e.coordinates),
+        ))
+    });
+    for (board, text) in explosions.chain(completions).chain(descriptions) {
+        log::info!("{}", text);
+        for (_, mut announcer) in announcers.iter_mut().filter(|(p, _)| p.get() == board) {
+            announcer.text = text.clone();
+        }
+    }
+}
+
+/// Exposes the changed announcements as polite live regions
+pub fn update_announcers(
+    mut commands: Commands,
+    announcers: Query<(Entity, &BoardAnnouncer), Changed<BoardAnnouncer>>,
+) {
+    for (entity, announcer) in announcers.iter() {
+        let mut node = NodeBuilder::new(Role::Status);
+        node.set_name(announcer.text.clone());
+        node.set_live(Live::Polite);
+        commands.entity(entity).insert(AccessibilityNode(node));
+    }
+}
+
+/// Coordinates and state of a tile, like `(3, 4): 2` or `(0, 1): flagged`
+fn tile_label(localization: &Localization, tile_map: &TileMap, coordinates: Coordinates) -> String {
+    localization.format(
+        "tile-label",
+        [
+            ("coordinates", coordinates.to_string().into()),
+            (
+                "state",
+                tile_state(localization, tile_map, coordinates).into(),
+            ),
+        ],
+    )
+}
+
+/// State of a tile as seen by the player, covered mines being shown once the board exploded
+fn tile_state(localization: &Localization, tile_map: &TileMap, coordinates: Coordinates) -> String {
+    let tile = tile_map.tile_at(coordinates);
+    if tile_map.is_revealed_at(coordinates) {
+        return match tile {
+            Tile::Bomb(count) => localization.format("tile-exploded", [("count", count.into())]),
+            Tile::BombNeighbor(count) => count.to_string(),
+            Tile::Empty => localization.message("tile-empty"),
+        };
+    }
+    match (tile_map.flags_at(coordinates), tile) {
+        (0, Tile::Bomb(_)) if tile_map.is_exploded() => localization.message("tile-mine"),
+        (0, _) if tile_map.is_questioned_at(coordinates) => localization.message("tile-questioned"),
+        (0, _) => localization.message("tile-covered"),
+        (flags, _) => localization.format("tile-flagged", [("count", flags.into())]),
+    }
+}
+
+/// Describes the tile at `coordinates` and each of its neighbors, with their direction
+fn describe_neighborhood(
+    localization: &Localization,
+    tile_map: &TileMap,
+    coordinates: Coordinates,
+) -> String {
+    let neighbors: Vec<String> = tile_map
+        .safe_square_at(coordinates)
+        .map(|c| {
+            localization.format(
+                "neighbor",
+                [
+                    (
+                        "direction",
+                        direction(localization, tile_map, coordinates, c).into(),
+                    ),
+                    ("state", tile_state(localization, tile_map, c).into()),
+                ],
+            )
+        })
+        .collect();
+    localization.format(
+        "neighborhood",
+        [
+            (
+                "tile",
+                tile_label(localization, tile_map, coordinates).into(),
+            ),
+            ("neighbors", neighbors.join(", ").into()),
+        ],
+    )
+}
+
+/// Compass direction from `from` to `to`, like `north east` or `2 south 1 west`, through the
+/// edges of wrapping maps
+fn direction(
+    localization: &Localization,
+    tile_map: &TileMap,
+    from: Coordinates,
+    to: Coordinates,
+) -> String {
+    let offset = |from: u16, to: u16, size: u16| {
+        let (delta, size) = (to as i32 - from as i32, size as i32);
+        match tile_map.is_wrapping() && delta.abs() > size / 2 {
+            true => delta - delta.signum() * size,
+            false => delta,
+        }
+    };
+    let dx = offset(from.x, to.x, tile_map.width());
+    let dy = offset(from.y, to.y, tile_map.height());
+    // Distances are only given to neighbors further than the adjacent tiles
+    let counted = dx.abs() > 1 || dy.abs() > 1;
+    let part = |delta: i32, positive: &str, negative: &str| {
+        let name = localization.message(if delta > 0 { positive } else { negative });
+        match (delta, counted) {
+            (0, _) => None,
+            (_, false) => Some(name),
+            (_, true) => Some(localization.format(
+                "direction-distance",
+                [("distance", delta.abs().into()), ("direction", name.into())],
+            )),
+        }
+    };
+    let parts: Vec<String> = [
+        part(dy, "direction-north", "direction-south"),
+        part(dx, "direction-east", "direction-west"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    match parts.is_empty() {
+        true => localization.message("direction-here"),
+        false => parts.join(" "),
+    }
+}
@@ -0,0 +1,256 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use rand::Rng;
+
+use crate::components::{BoardShake, Bomb, Confetti, Coordinates, Uncover, UncoverDelay};
+use crate::events::{BoardCompletedEvent, BombExplosionEvent, SkipAnimationsEvent};
+use crate::resources::{AnimationSettings, Board, BoardAssets, SkinTile, Tile};
+use crate::systems::uncover::wave_distance;
+use crate::EXPLODED_COLOR;
+
+/// Tile covers, with their skin sprite if skinned
+type Covers<'w, 's> = Query<'w, 's, Option<&'static mut TextureAtlasSprite>, Without<Bomb>>;
+
+/// Confetti thrown over a completed board
+const CONFETTI_COUNT: usize = 80;
+
+/// Mine tiles, drawn as sprites or skin cells
+type MineTiles<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Parent,
+        &'static Coordinates,
+        AnyOf<(&'static mut Sprite, &'static mut TextureAtlasSprite)>,
+    ),
+    With<Bomb>,
+>;
+
+/// Draws the exploded mines, uncovers the other mines one after the other from the explosion,
+/// crosses out the wrong marks and shakes the board
+pub fn animate_explosions(
+    mut commands: Commands,
+    settings: Res<AnimationSettings>,
+    mut boards: Query<(&mut Board, &Transform, Option<&BoardShake>)>,
+    mut explosion_evr: EventReader<BombExplosionEvent>,
+    mut tiles: MineTiles,
+    mut covers: Covers,
+) {
+    for event in explosion_evr.iter() {
+        let Ok((mut board, transform, shake)) = boards.get_mut(event.board) else {
+            continue;
+        };
+        // Mines are only revealed when exploding
+        let exploded: Vec<Coordinates> = board
+            .tile_map
+            .iter()
+            .filter(|(c, tile)| matches!(tile, Tile::Bomb(_)) && board.tile_map.is_revealed_at(*c))
+            .map(|(c, _)| c)
+            .collect();
+        for (parent, coordinates, (sprite, skin_sprite)) in tiles.iter_mut() {
+            if parent.get() != event.board || !exploded.contains(coordinates) {
+                continue;
+            }
+            if let Some(mut sprite) = sprite {
+                sprite.color = EXPLODED_COLOR;
+            }
+            if let Some(mut sprite) = skin_sprite {
+                sprite.index = SkinTile::ExplodedMine.index();
+            }
+        }
+
+        let mines = board.take_mine_covers();
+        let distance = |c: Coordinates| {
+            exploded
+                .iter()
+                .map(|origin| wave_distance(&board.tile_map, *origin, c))
+                .min()
+                .unwrap_or_default()
+        };
+        let mut chain: Vec<Coordinates> = mines.iter().map(|(c, _)| *c).collect();
+        chain.sort_by_key(|c| (distance(*c), c.y, c.x));
+        chain.dedup();
+        let step = settings
+            .mine_delay
+            .min(settings.max_chain_duration / chain.len().max(1) as f32);
+        let ranks: HashMap<Coordinates, usize> =
+            chain.into_iter().enumerate().map(|(i, c)| (c, i)).collect();
+        for (coordinates, entity) in mines {
+            let delay = (ranks[&coordinates] + 1) as f32 * step;
+            match settings.reduced_motion {
+                true => commands.entity(entity).insert(Uncover),
+                false => commands
+                    .entity(entity)
+                    .insert(UncoverDelay(Timer::from_seconds(delay, TimerMode::Once))),
+            };
+        }
+
+        let wrong_marks: Vec<(Coordinates, Entity)> = board
+            .covered_tiles
+            .iter()
+            .filter(|(c, _)| board.tile_map.is_flagged_at(**c) && !board.tile_map.is_bomb_at(**c))
+            .map(|(c, entity)| (*c, *entity))
+            .collect();
+        for (coordinates, cover) in wrong_marks {
+            // Ghost tiles mirror the marks of their tile
+            let entities =
+                std::iter::once(cover).chain(board.ghost_covers(&coordinates).iter().copied());
+            for entity in entities {
+                cross_out(&mut commands, entity, board.tile_size, &mut covers);
+            }
+        }
+
+        if !settings.reduced_motion {
+            commands.entity(event.board).insert(BoardShake {
+                timer: Timer::from_seconds(settings.shake_duration, TimerMode::Once),
+                origin: shake.map_or(transform.translation, |s| s.origin),
+                amplitude: settings.shake_amplitude * board.tile_size,
+            });
+        }
+    }
+}
+
+/// Crosses out the wrongly marked `cover`, switching skinned covers to the wrong flag cell
+fn cross_out(commands: &mut Commands, cover: Entity, size: f32, covers: &mut Covers) {
+    let Ok(skin_sprite) = covers.get_mut(cover) else {
+        return;
+    };
+    if let Some(mut sprite) = skin_sprite {
+        sprite.index = SkinTile::WrongFlag.index();
+        return;
+    }
+    commands.entity(cover).with_children(|parent| {
+        for angle in [FRAC_PI_4, -FRAC_PI_4] {
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: EXPLODED_COLOR,
+                    custom_size: Some(Vec2::new(size * 0.8, size * 0.1)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(0., 0., 2.)
+                    .with_rotation(Quat::from_rotation_z(angle)),
+                ..Default::default()
+            });
+        }
+    });
+}
+
+/// Throws confetti in the bomb counter colors over the completed boards
+pub fn celebrate_wins(
+    mut commands: Commands,
+    settings: Res<AnimationSettings>,
+    board_assets: Option<Res<BoardAssets>>,
+    boards: Query<(&Board, Option<&BoardAssets>)>,
+    mut completed_evr: EventReader<BoardCompletedEvent>,
+) {
+    let mut rng = rand::thread_rng();
+    for event in completed_evr.iter() {
+        let Ok((board, own_assets)) = boards.get(event.board) else {
+            continue;
+        };
+        if settings.reduced_motion {
+            continue;
+        }
+        let colors = own_assets
+            .or(board_assets.as_deref())
+            .map_or_else(BoardAssets::default_colors, |a| {
+                a.bomb_counter_colors.clone()
+            });
+        let size = board.bounds.size;
+        commands.entity(event.board).with_children(|parent| {
+            for color in colors.iter().cycle().take(CONFETTI_COUNT) {
+                let angle = FRAC_PI_2 + rng.gen_range(-0.6..0.6);
+                let speed = rng.gen_range(0.8..1.4) * size.y;
+                parent
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            color: *color,
+                            custom_size: Some(Vec2::new(0.4, 0.2) * board.tile_size),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation((size / 2.).extend(10.)),
+                        ..Default::default()
+                    })
+                    .insert(Name::new("Confetti"))
+                    .insert(Confetti {
+                        timer: Timer::from_seconds(settings.celebration_duration, TimerMode::Once),
+                        velocity: Vec2::from_angle(angle) * speed,
+                        gravity: 1.5 * size.y,
+                        spin: rng.gen_range(-8.0..8.0),
+                    });
+            }
+        });
+    }
+}
+
+/// Moves the confetti, fading them out over the last third of their time
+pub fn update_confetti(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut confetti: Query<(Entity, &mut Confetti, &mut Transform, &mut Sprite)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut confetti, mut transform, mut sprite) in confetti.iter_mut() {
+        if confetti.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        confetti.velocity.y -= confetti.gravity * delta;
+        transform.translation += (confetti.velocity * delta).extend(0.);
+        transform.rotate_z(confetti.spin * delta);
+        sprite
+            .color
+            .set_a((confetti.timer.percent_left() * 3.).min(1.));
+    }
+}
+
+/// Shakes the exploded boards around their origin, the shake fading over time
+pub fn shake_boards(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut boards: Query<(Entity, &mut BoardShake, &mut Transform)>,
+) {
+    for (entity, mut shake, mut transform) in boards.iter_mut() {
+        if shake.timer.tick(time.delta()).finished() {
+            transform.translation = shake.origin;
+            commands.entity(entity).remove::<BoardShake>();
+            continue;
+        }
+        let t = shake.timer.elapsed_secs();
+        let offset = Vec2::new((t * 83.).sin(), (t * 61.).cos())
+            * shake.amplitude
+            * shake.timer.percent_left();
+        transform.translation = shake.origin + offset.extend(0.);
+    }
+}
+
+/// Ends the running animations on a [`SkipAnimationsEvent`], and as soon as they start with
+/// [`AnimationSettings::reduced_motion`]
+pub fn skip_animations(
+    mut commands: Commands,
+    settings: Res<AnimationSettings>,
+    mut skip_evr: EventReader<SkipAnimationsEvent>,
+    delays: Query<Entity, With<UncoverDelay>>,
+    mut shakes: Query<(Entity, &BoardShake, &mut Transform)>,
+    confetti: Query<Entity, With<Confetti>>,
+) {
+    let skipped = skip_evr.iter().count() > 0;
+    if !skipped && !settings.reduced_motion {
+        return;
+    }
+    for entity in delays.iter() {
+        commands
+            .entity(entity)
+            .remove::<UncoverDelay>()
+            .insert(Uncover);
+    }
+    for (entity, shake, mut transform) in shakes.iter_mut() {
+        transform.translation = shake.origin;
+        commands.entity(entity).remove::<BoardShake>();
+    }
+    for entity in confetti.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
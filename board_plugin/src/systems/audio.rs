@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::events::{
+    BoardCompletedEvent, BombExplosionEvent, TileChordEvent, TileMarkedEvent, TilesRevealedEvent,
+};
+use crate::resources::{AudioSettings, BoardAssets, BoardSound};
+
+/// Readers of the events played as sounds
+type SoundEventReaders<'w, 's> = (
+    EventReader<'w, 's, TilesRevealedEvent>,
+    EventReader<'w, 's, TileMarkedEvent>,
+    EventReader<'w, 's, TileChordEvent>,
+    EventReader<'w, 's, BombExplosionEvent>,
+    EventReader<'w, 's, BoardCompletedEvent>,
+);
+
+/// Plays the sounds of the board events, with the sound files of the board assets.
+///
+/// A sound plays once per frame, explosions and wins covering the reveals leading to them
+pub fn play_board_sounds(
+    audio: Res<Audio>,
+    settings: Res<AudioSettings>,
+    board_assets: Option<Res<BoardAssets>>,
+    boards: Query<&BoardAssets>,
+    (mut revealed_evr, mut marked_evr, mut chord_evr, mut explosion_evr, mut completed_evr): SoundEventReaders,
+) {
+    let revealed = revealed_evr.iter().map(|e| {
+        let sound = match e.count {
+            1 => BoardSound::Reveal,
+            _ => BoardSound::Cascade,
+        };
+        (e.board, sound)
+    });
+    let marked = marked_evr.iter().map(|e| {
+        let sound = match e.marks {
+            0 => BoardSound::Unflag,
+            _ => BoardSound::Flag,
+        };
+        (e.board, sound)
+    });
+    let chords = chord_evr.iter().map(|e| (e.board, BoardSound::Chord));
+    let mut sounds: Vec<(Entity, BoardSound)> = revealed.chain(marked).chain(chords).collect();
+    let endings: Vec<_> = explosion_evr
+        .iter()
+        .map(|e| (e.board, BoardSound::Explosion))
+        .chain(completed_evr.iter().map(|e| (e.board, BoardSound::Win)))
+        .collect();
+    if !endings.is_empty() {
+        sounds.retain(|(_, s)| !matches!(s, BoardSound::Reveal | BoardSound::Cascade));
+        sounds.extend(endings);
+    }
+    if settings.muted {
+        return;
+    }
+    let mut played = HashSet::new();
+    for (board, sound) in sounds {
+        if !played.insert(sound) {
+            continue;
+        }
+        let Some(assets) = boards.get(board).ok().or(board_assets.as_deref()) else {
+            continue;
+        };
+        let Some(source) = assets.sounds.get(&sound) else {
+            continue;
+        };
+        let volume = settings.volume(sound);
+        if volume > 0. {
+            audio.play_with_settings(source.clone(), PlaybackSettings::ONCE.with_volume(volume));
+        }
+    }
+}
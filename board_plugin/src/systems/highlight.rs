@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy::utils::HashMap;
+use bevy::window::PrimaryWindow;
+
+use crate::components::{BoardInput, BoardPlayer, Coordinates, SpectatedBoard};
+use crate::resources::{Board, SkinTile};
+use crate::systems::input::cursor_world_position;
+
+/// Color the hovered covers are tinted towards
+const HOVER_COLOR: Color = Color::rgb(1., 0.95, 0.6);
+/// Brightness of the pressed covers of unskinned boards
+const PRESSED_BRIGHTNESS: f32 = 0.6;
+
+/// Look of a highlighted cover
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Highlight {
+    Hovered,
+    Pressed,
+}
+
+/// Cover of a highlighted tile, with its color before the highlight
+#[derive(Debug, Copy, Clone)]
+pub struct HighlightedCover {
+    board: Entity,
+    coordinates: Coordinates,
+    color: Color,
+}
+
+/// Highlights the covered tile under the mouse, drawn pressed while a button is held, like the
+/// covered neighbors of a revealed tile while a chord is held.
+///
+/// Skinned covers are pressed with the [`SkinTile::Pressed`] cell, marked covers are never
+/// pressed
+pub fn highlight_tiles(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&RenderLayers>)>,
+    boards: Query<(Entity, &Board, &BoardPlayer), Without<SpectatedBoard>>,
+    buttons: Res<Input<MouseButton>>,
+    mut sprites: Query<AnyOf<(&mut Sprite, &mut TextureAtlasSprite)>>,
+    mut highlighted: Local<HashMap<Entity, HighlightedCover>>,
+) {
+    // The previous highlights are undone, marks may have changed since
+    for (entity, cover) in highlighted.drain() {
+        let Ok((sprite, skin_sprite)) = sprites.get_mut(entity) else {
+            continue;
+        };
+        if let Some(mut sprite) = sprite {
+            sprite.color = cover.color;
+        }
+        if let Some(mut sprite) = skin_sprite {
+            sprite.color = cover.color;
+            let marked = boards
+                .get(cover.board)
+                .is_ok_and(|(_, board, _)| board.is_marked(&cover.coordinates));
+            sprite.index = match marked {
+                true => SkinTile::Flag,
+                false => SkinTile::Covered,
+            }
+            .index();
+        }
+    }
+
+    let Some(position) = cursor_world_position(&windows, &cameras) else {
+        return;
+    };
+    let Some((entity, board, coordinates)) = boards.iter().find_map(|(entity, board, player)| {
+        let over = board.is_exploded() || board.is_completed();
+        if !matches!(player.input, BoardInput::Mouse) || over {
+            return None;
+        }
+        Some((entity, board, board.world_to_coordinates(position)?))
+    }) else {
+        return;
+    };
+    let highlights: Vec<(Coordinates, Highlight)> = if board.tile_map.is_revealed_at(coordinates) {
+        // Held chords press the covered neighbors
+        match buttons.pressed(MouseButton::Middle) {
+            true => board
+                .tile_map
+                .safe_square_at(coordinates)
+                .map(|c| (c, Highlight::Pressed))
+                .collect(),
+            false => Vec::new(),
+        }
+    } else if buttons.pressed(MouseButton::Left) {
+        vec![(coordinates, Highlight::Pressed)]
+    } else {
+        vec![(coordinates, Highlight::Hovered)]
+    };
+
+    for (coordinates, highlight) in highlights {
+        let Some(cover) = board.covered_tiles.get(&coordinates) else {
+            continue;
+        };
+        if highlight == Highlight::Pressed && board.is_marked(&coordinates) {
+            continue;
+        }
+        let Ok((sprite, skin_sprite)) = sprites.get_mut(*cover) else {
+            continue;
+        };
+        let color = match (sprite, skin_sprite) {
+            (Some(mut sprite), _) => {
+                let color = sprite.color;
+                sprite.color = match highlight {
+                    Highlight::Hovered => mix(color, HOVER_COLOR, 0.35),
+                    Highlight::Pressed => color * PRESSED_BRIGHTNESS,
+                };
+                color
+            }
+            (_, Some(mut sprite)) => {
+                let color = sprite.color;
+                match highlight {
+                    Highlight::Hovered => sprite.color = mix(color, HOVER_COLOR, 0.35),
+                    Highlight::Pressed => sprite.index = SkinTile::Pressed.index(),
+                }
+                color
+            }
+            (None, None) => continue,
+        };
+        highlighted.insert(
+            *cover,
+            HighlightedCover {
+                board: entity,
+                coordinates,
+                color,
+            },
+        );
+    }
+}
+
+/// Blends `color` towards `target` by `amount`, keeping its alpha
+fn mix(color: Color, target: Color, amount: f32) -> Color {
+    let [r, g, b, a] = color.as_rgba_f32();
+    let [tr, tg, tb, _] = target.as_rgba_f32();
+    Color::rgba(
+        r + (tr - r) * amount,
+        g + (tg - g) * amount,
+        b + (tb - b) * amount,
+        a,
+    )
+}
@@ -1,30 +1,208 @@
+use bevy::render::view::RenderLayers;
+use bevy::window::PrimaryWindow;
 use bevy::{log, prelude::*};
 
 use crate::{
-    events::{TileMarkEvent, TileTriggerEvent},
+    components::{BoardCursor, BoardInput, BoardPlayer, Coordinates, SpectatedBoard},
+    events::{DescribeNeighborhoodEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent},
     resources::Board,
 };
 
+/// Tile action requested by a player
+#[derive(Debug, Copy, Clone)]
+enum TileInput {
+    Trigger,
+    Mark,
+    Chord,
+}
+
+/// Board event writers
+pub(crate) type TileEventWriters<'w> = (
+    EventWriter<'w, TileTriggerEvent>,
+    EventWriter<'w, TileMarkEvent>,
+    EventWriter<'w, TileChordEvent>,
+);
+
+fn send_tile_input(
+    (trigger_ewr, mark_ewr, chord_ewr): &mut TileEventWriters,
+    input: TileInput,
+    board: Entity,
+    coordinates: Coordinates,
+) {
+    match input {
+        TileInput::Trigger => {
+            log::info!("Trying to uncover tile on {}", coordinates);
+            trigger_ewr.send(TileTriggerEvent { board, coordinates });
+        }
+        TileInput::Mark => {
+            log::info!("Trying to mark tile on {}", coordinates);
+            mark_ewr.send(TileMarkEvent { board, coordinates });
+        }
+        TileInput::Chord => {
+            log::info!("Trying to chord tile on {}", coordinates);
+            chord_ewr.send(TileChordEvent { board, coordinates });
+        }
+    }
+}
+
+/// Mouse input of the boards bound to the mouse, picking the hovered tile through the camera
+/// viewport under the cursor.
+///
+/// Like in classic minesweeper, tiles are revealed and chorded once the button is released, the
+/// held tiles being drawn pressed by [`super::highlight::highlight_tiles`]
 pub fn input_handling(
-    windows: Query<&Window>,
-    board: Res<Board>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&RenderLayers>)>,
+    boards: Query<(Entity, &Board, &BoardPlayer), Without<SpectatedBoard>>,
     buttons: Res<Input<MouseButton>>,
-    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
-    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut event_writers: TileEventWriters,
 ) {
-    let window = windows.single();
-    let position = window.cursor_position();
-    if let Some(pos) = position {
-        let tile_coordinates = board.mouse_position(window, pos);
-        if let Some(coordinates) = tile_coordinates {
-            if buttons.just_pressed(MouseButton::Left) {
-                log::info!("Trying to uncover tile on {}", coordinates);
-                tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+    let input = if buttons.just_released(MouseButton::Left) {
+        TileInput::Trigger
+    } else if buttons.just_pressed(MouseButton::Right) {
+        TileInput::Mark
+    } else if buttons.just_released(MouseButton::Middle) {
+        TileInput::Chord
+    } else {
+        return;
+    };
+    let Some(position) = cursor_world_position(&windows, &cameras) else {
+        return;
+    };
+    for (entity, board, player) in boards.iter() {
+        if !matches!(player.input, BoardInput::Mouse) {
+            continue;
+        }
+        if let Some(coordinates) = board.world_to_coordinates(position) {
+            send_tile_input(&mut event_writers, input, entity, coordinates);
+            return;
+        }
+    }
+}
+
+/// World position of the mouse cursor, seen through the board camera viewport under it
+pub(crate) fn cursor_world_position(
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    cameras: &Query<(&Camera, &GlobalTransform, Option<&RenderLayers>)>,
+) -> Option<Vec2> {
+    let window = windows.get_single().ok()?;
+    let cursor = window.cursor_position()?;
+    // The window cursor origin is the bottom left corner, the viewport one the top left corner
+    let cursor_from_top = Vec2::new(cursor.x, window.height() - cursor.y);
+    cameras.iter().find_map(|(camera, transform, layers)| {
+        // Cameras not rendering the boards, like HUD overlays, are skipped
+        if !camera.is_active || !layers.copied().unwrap_or_default().intersects(&default()) {
+            return None;
+        }
+        let (min, max) = camera.logical_viewport_rect()?;
+        if cursor_from_top.cmplt(min).any() || cursor_from_top.cmpge(max).any() {
+            return None;
+        }
+        let viewport_position = Vec2::new(cursor.x - min.x, max.y - cursor_from_top.y);
+        camera.viewport_to_world_2d(transform, viewport_position)
+    })
+}
+
+/// Keyboard and gamepad input of the boards controlled with a tile cursor
+pub fn cursor_input_handling(
+    keys: Res<Input<KeyCode>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    boards: Query<(&Board, &BoardPlayer), Without<SpectatedBoard>>,
+    mut cursors: Query<(&Parent, &mut BoardCursor, &mut Transform)>,
+    mut event_writers: TileEventWriters,
+    mut describe_ewr: EventWriter<DescribeNeighborhoodEvent>,
+) {
+    for (parent, mut cursor, mut transform) in cursors.iter_mut() {
+        let Ok((board, player)) = boards.get(parent.get()) else {
+            continue;
+        };
+        let (delta, input, describe) = match &player.input {
+            BoardInput::Mouse => continue,
+            BoardInput::Keyboard(bindings) => {
+                let pressed = |key| keys.just_pressed(key);
+                (
+                    direction(
+                        pressed(bindings.left),
+                        pressed(bindings.right),
+                        pressed(bindings.down),
+                        pressed(bindings.up),
+                    ),
+                    action(
+                        pressed(bindings.reveal),
+                        pressed(bindings.mark),
+                        pressed(bindings.chord),
+                    ),
+                    pressed(bindings.describe),
+                )
             }
-            if buttons.just_pressed(MouseButton::Right) {
-                log::info!("Trying to mark tile on {}", coordinates);
-                tile_mark_ewr.send(TileMarkEvent(coordinates));
+            BoardInput::Gamepad(bindings) => {
+                let pressed = |button| {
+                    gamepad_buttons.just_pressed(GamepadButton::new(bindings.gamepad, button))
+                };
+                (
+                    direction(
+                        pressed(GamepadButtonType::DPadLeft),
+                        pressed(GamepadButtonType::DPadRight),
+                        pressed(GamepadButtonType::DPadDown),
+                        pressed(GamepadButtonType::DPadUp),
+                    ),
+                    action(
+                        pressed(bindings.reveal),
+                        pressed(bindings.mark),
+                        pressed(bindings.chord),
+                    ),
+                    pressed(bindings.describe),
+                )
             }
+        };
+        if delta != (0, 0) {
+            cursor.0 = move_cursor(board, cursor.0, delta);
+            let center = board
+                .tile_map
+                .topology()
+                .tile_center(cursor.0, board.tile_size);
+            transform.translation = center.extend(transform.translation.z);
+        }
+        if let Some(input) = input {
+            send_tile_input(&mut event_writers, input, parent.get(), cursor.0);
+        }
+        if describe {
+            describe_ewr.send(DescribeNeighborhoodEvent {
+                board: parent.get(),
+                coordinates: cursor.0,
+            });
         }
     }
 }
+
+/// Cursor move of the pressed direction keys
+fn direction(left: bool, right: bool, down: bool, up: bool) -> (i32, i32) {
+    (right as i32 - left as i32, up as i32 - down as i32)
+}
+
+/// First pressed action
+fn action(reveal: bool, mark: bool, chord: bool) -> Option<TileInput> {
+    [
+        (reveal, TileInput::Trigger),
+        (mark, TileInput::Mark),
+        (chord, TileInput::Chord),
+    ]
+    .into_iter()
+    .find_map(|(pressed, input)| pressed.then_some(input))
+}
+
+/// Moves a cursor by `delta`, across the edges of wrapping boards
+fn move_cursor(board: &Board, cursor: Coordinates, (dx, dy): (i32, i32)) -> Coordinates {
+    let tile_map = &board.tile_map;
+    let (width, height) = (tile_map.width() as i32, tile_map.height() as i32);
+    let (x, y) = (cursor.x as i32 + dx, cursor.y as i32 + dy);
+    let (x, y) = if tile_map.is_wrapping() {
+        (x.rem_euclid(width), y.rem_euclid(height))
+    } else {
+        (x.clamp(0, width - 1), y.clamp(0, height - 1))
+    };
+    Coordinates {
+        x: x as u16,
+        y: y as u16,
+    }
+}
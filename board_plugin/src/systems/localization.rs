@@ -0,0 +1,36 @@
+use bevy::{log, prelude::*};
+
+use crate::resources::{Catalog, Locale, Localization};
+
+/// Loads the existing catalogs of the [`Locale`] languages whenever it changes, and rebuilds the
+/// [`Localization`] bundles once they are loaded or their files reloaded
+pub fn load_catalogs(
+    locale: Res<Locale>,
+    asset_server: Res<AssetServer>,
+    catalogs: Res<Assets<Catalog>>,
+    mut catalog_evr: EventReader<AssetEvent<Catalog>>,
+    mut localization: ResMut<Localization>,
+) {
+    if locale.is_changed() {
+        log::info!("Using the {} language", locale.language);
+        localization.catalogs = locale
+            .languages()
+            .into_iter()
+            .filter_map(|language| {
+                let path = Locale::catalog_path(&language);
+                // Most languages fall back to a missing base or default language catalog
+                asset_server.asset_io().get_metadata(path.as_ref()).ok()?;
+                Some((language, asset_server.load(path)))
+            })
+            .collect();
+    }
+    let loaded = catalog_evr.iter().any(|event| match event {
+        AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+            localization.catalogs.iter().any(|(_, h)| h == handle)
+        }
+        AssetEvent::Removed { .. } => false,
+    });
+    if loaded || locale.is_changed() {
+        localization.build_bundles(&catalogs);
+    }
+}
@@ -1,45 +1,171 @@
-use crate::{events::TileMarkEvent, Board, BoardAssets};
+use crate::components::{Coordinates, RemoteBoard};
+use crate::events::{TileMarkEvent, TileMarkedEvent};
+use crate::resources::SkinTile;
+use crate::{Board, BoardAssets};
 use bevy::log;
 use bevy::prelude::*;
 
+/// Tile covers, with their flags and their skin sprite if skinned
+pub(crate) type Covers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static Children>,
+        Option<&'static mut TextureAtlasSprite>,
+    ),
+>;
+
 pub fn mark_tiles(
     mut commands: Commands,
-    mut board: ResMut<Board>,
-    board_assets: Res<BoardAssets>,
+    mut boards: Query<(&mut Board, Option<&BoardAssets>), Without<RemoteBoard>>,
+    board_assets: Option<Res<BoardAssets>>,
     mut tile_mark_event_rdr: EventReader<TileMarkEvent>,
-    query: Query<&Children>,
+    mut tile_marked_event_wr: EventWriter<TileMarkedEvent>,
+    mut covers: Covers,
 ) {
     for event in tile_mark_event_rdr.iter() {
-        if let Some((entity, mark)) = board.try_toggle_mark(&event.0) {
-            if mark {
-                // Add flag
+        let Ok((mut board, own_assets)) = boards.get_mut(event.board) else {
+            continue;
+        };
+        // Exploded and completed boards are over
+        if board.is_exploded() || board.is_completed() {
+            continue;
+        }
+        let Some(board_assets) = own_assets.or(board_assets.as_deref()) else {
+            continue;
+        };
+        let Some((entity, marks)) = board.try_toggle_mark(&event.coordinates) else {
+            continue;
+        };
+        log::trace!("Tile {} now has {} marks", event.coordinates, marks);
+        update_flags(
+            &mut commands,
+            &board,
+            (event.coordinates, entity),
+            marks,
+            board_assets,
+            &mut covers,
+        );
+        tile_marked_event_wr.send(TileMarkedEvent {
+            board: event.board,
+            coordinates: event.coordinates,
+            marks,
+        });
+    }
+}
+
+/// Replaces the flag sprites, or question mark, of the tile at `coordinates` and its ghost
+/// tiles
+pub(crate) fn update_flags(
+    commands: &mut Commands,
+    board: &Board,
+    (coordinates, cover): (Coordinates, Entity),
+    marks: u8,
+    board_assets: &BoardAssets,
+    covers: &mut Covers,
+) {
+    let questioned = board.tile_map.is_questioned_at(coordinates);
+    // Ghost tiles mirror the marks of their tile
+    let entities = std::iter::once(cover).chain(board.ghost_covers(&coordinates).iter().copied());
+    for entity in entities {
+        let Ok((children, sprite)) = covers.get_mut(entity) else {
+            continue;
+        };
+        // Remove previous flag
+        for child in children.into_iter().flatten() {
+            commands.entity(*child).despawn_recursive();
+        }
+        // Skinned covers switch to the flag cell, only missing the count of stacked flags
+        if let Some(mut sprite) = sprite {
+            sprite.index = match marks {
+                0 => SkinTile::Covered,
+                _ => SkinTile::Flag,
+            }
+            .index();
+            if marks > 1 {
                 commands.entity(entity).with_children(|parent| {
-                    parent
-                        .spawn(SpriteBundle {
-                            texture: board_assets.flag_material.texture.clone(),
-                            sprite: Sprite {
-                                custom_size: Some(Vec2::splat(board.tile_size)),
-                                color: board_assets.flag_material.color,
-                                ..Default::default()
-                            },
-                            transform: Transform::from_xyz(0., 0., 1.),
-                            ..Default::default()
-                        })
-                        .insert(Name::new("Flag"));
+                    spawn_mark_count(parent, marks, board.tile_size, board_assets);
                 });
-            } else {
-                // Remove flag
-                let children = match query.get(entity) {
-                    Ok(c) => c,
-                    Err(e) => {
-                        log::error!("Failed to retrieve flag entity components: {}", e);
-                        continue;
-                    }
-                };
-                for child in children.iter() {
-                    commands.entity(*child).despawn_recursive();
-                }
             }
+        } else if marks > 0 {
+            // Add flag
+            commands.entity(entity).with_children(|parent| {
+                spawn_flag(parent, marks, board.tile_size, board_assets);
+            });
+        }
+        if questioned {
+            commands.entity(entity).with_children(|parent| {
+                spawn_question_mark(parent, board.tile_size, board_assets);
+            });
         }
     }
 }
+
+/// Spawns the flag sprite of a cover with `marks` flags
+pub(crate) fn spawn_flag(
+    parent: &mut ChildBuilder,
+    marks: u8,
+    tile_size: f32,
+    board_assets: &BoardAssets,
+) {
+    parent
+        .spawn(SpriteBundle {
+            texture: board_assets.flag_material.texture.clone(),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(tile_size)),
+                color: board_assets.flag_material.color,
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., 0., 1.),
+            ..Default::default()
+        })
+        .insert(Name::new("Flag"))
+        .with_children(|parent| {
+            if marks > 1 {
+                spawn_mark_count(parent, marks, tile_size, board_assets);
+            }
+        });
+}
+
+/// Spawns the count of stacked flags in the bottom right corner
+pub(crate) fn spawn_mark_count(
+    parent: &mut ChildBuilder,
+    marks: u8,
+    tile_size: f32,
+    board_assets: &BoardAssets,
+) {
+    parent.spawn(Text2dBundle {
+        text: Text::from_section(
+            marks.to_string(),
+            TextStyle {
+                font: board_assets.bomb_counter_font.clone(),
+                font_size: tile_size / 2.,
+                color: board_assets.bomb_counter_color(marks),
+            },
+        ),
+        transform: Transform::from_xyz(tile_size / 4., -tile_size / 4., 1.),
+        ..Default::default()
+    });
+}
+
+/// Spawns the question mark of a cover, drawn in the flag color
+pub(crate) fn spawn_question_mark(
+    parent: &mut ChildBuilder,
+    tile_size: f32,
+    board_assets: &BoardAssets,
+) {
+    parent
+        .spawn(Text2dBundle {
+            text: Text::from_section(
+                "?",
+                TextStyle {
+                    font: board_assets.bomb_counter_font.clone(),
+                    font_size: tile_size * 0.8,
+                    color: board_assets.flag_material.color,
+                },
+            ),
+            transform: Transform::from_xyz(0., 0., 1.),
+            ..Default::default()
+        })
+        .insert(Name::new("Question Mark"));
+}
@@ -1,3 +1,11 @@
+pub mod accessibility;
+pub mod animation;
+pub mod audio;
+pub mod highlight;
 pub mod input;
+pub mod localization;
 pub mod mark;
+pub mod net;
+pub mod spectate;
+pub mod theme;
 pub mod uncover;
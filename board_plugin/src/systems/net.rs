@@ -0,0 +1,328 @@
+use std::sync::mpsc::TryRecvError;
+
+use bevy::render::view::RenderLayers;
+use bevy::window::PrimaryWindow;
+use bevy::{log, prelude::*};
+
+use crate::components::{Coordinates, RemoteBoard, RemoteCursor};
+use crate::events::{TileChordEvent, TileMarkEvent, TileMarkedEvent, TileTriggerEvent};
+use crate::net::{ClientMessage, NetClient, PlayerId, RemotePlayer, ServerMessage};
+use crate::resources::{Action, BoardAssets, BoardOptions};
+use crate::systems::input::cursor_world_position;
+use crate::systems::mark::{update_flags, Covers};
+use crate::systems::uncover::{uncover_revealed, RevealEventWriters};
+use crate::Board;
+
+/// Remote boards whose players changed, or just built
+type ChangedRemoteBoards<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Board,
+        &'static RemoteBoard,
+        Option<&'static BoardAssets>,
+    ),
+    Or<(Changed<RemoteBoard>, Added<Board>)>,
+>;
+
+/// Joins the server game, the server answering with its board
+pub fn join_server(client: Res<NetClient>) {
+    let message = ClientMessage::Join {
+        name: client.name().to_string(),
+    };
+    if let Err(e) = client.send(&message) {
+        log::error!("Failed to join the server, {}", e);
+    }
+}
+
+/// Forwards the server messages as events.
+///
+/// Messages following a welcome are held until its board is built, to apply on the new board
+pub fn receive_server_messages(
+    mut commands: Commands,
+    client: Res<NetClient>,
+    pending_boards: Query<(), (With<RemoteBoard>, Without<Board>)>,
+    mut messages: EventWriter<ServerMessage>,
+) {
+    if !pending_boards.is_empty() {
+        return;
+    }
+    loop {
+        match client.try_recv() {
+            Ok(message) => {
+                let welcome = matches!(message, ServerMessage::Welcome { .. });
+                messages.send(message);
+                if welcome {
+                    return;
+                }
+            }
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                log::error!("Disconnected from the server");
+                commands.remove_resource::<NetClient>();
+                return;
+            }
+        }
+    }
+}
+
+/// Replaces the remote board with the board of the server, keeping the local display options
+pub fn spawn_remote_board(
+    mut commands: Commands,
+    board_options: Option<Res<BoardOptions>>,
+    boards: Query<Entity, With<RemoteBoard>>,
+    mut messages: EventReader<ServerMessage>,
+) {
+    for message in messages.iter() {
+        let ServerMessage::Welcome {
+            player,
+            mode,
+            options,
+            players,
+        } = message
+        else {
+            continue;
+        };
+        log::info!("Joined the {:?} server as player {}", mode, player);
+        for entity in boards.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        let local = board_options.as_deref().cloned().unwrap_or_default();
+        commands
+            .spawn(BoardOptions {
+                position: local.position,
+                tile_size: local.tile_size,
+                tile_padding: local.tile_padding,
+                reveal_animation: local.reveal_animation,
+                ..options.clone()
+            })
+            .insert(RemoteBoard {
+                player: *player,
+                mode: *mode,
+                players: players.iter().map(|p| (p.id, p.clone())).collect(),
+                progress: Default::default(),
+                ranking: None,
+            });
+    }
+}
+
+/// Tracks the other players joining, leaving, moving their cursor and progressing
+pub fn update_remote_players(
+    mut boards: Query<&mut RemoteBoard>,
+    mut messages: EventReader<ServerMessage>,
+) {
+    for message in messages.iter() {
+        for mut board in boards.iter_mut() {
+            match message {
+                ServerMessage::Joined(player) => {
+                    log::info!("{} joined", player.name);
+                    board.players.insert(player.id, player.clone());
+                }
+                ServerMessage::Left(id) => {
+                    if let Some(player) = board.players.remove(id) {
+                        log::info!("{} left", player.name);
+                    }
+                    board.progress.remove(id);
+                }
+                ServerMessage::Cursor {
+                    player,
+                    coordinates,
+                } => {
+                    if let Some(player) = board.players.get_mut(player) {
+                        player.cursor = *coordinates;
+                    }
+                }
+                ServerMessage::Progress(progress) => {
+                    board.progress.insert(progress.player, progress.clone());
+                }
+                ServerMessage::Ranking(ranking) => {
+                    board.ranking = Some(ranking.clone());
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Applies the reveals and marks played on the server
+pub fn apply_remote_updates(
+    mut commands: Commands,
+    mut boards: Query<(Entity, &mut Board, &BoardOptions, Option<&BoardAssets>), With<RemoteBoard>>,
+    board_assets: Option<Res<BoardAssets>>,
+    mut covers: Covers,
+    mut messages: EventReader<ServerMessage>,
+    (mut reveal_event_wrs, mut tile_marked_event_wr): (
+        RevealEventWriters,
+        EventWriter<TileMarkedEvent>,
+    ),
+) {
+    for message in messages.iter() {
+        for (entity, mut board, options, own_assets) in boards.iter_mut() {
+            match message {
+                ServerMessage::Revealed { tiles, .. } => {
+                    let Some(origin) = tiles.first().copied() else {
+                        continue;
+                    };
+                    let revealed = board.reveal_tiles(tiles.clone());
+                    uncover_revealed(
+                        &mut commands,
+                        (entity, &board),
+                        origin,
+                        revealed,
+                        options.reveal_animation.as_ref(),
+                        &mut reveal_event_wrs,
+                    );
+                }
+                ServerMessage::Marked {
+                    coordinates, marks, ..
+                } => {
+                    let Some(board_assets) = own_assets.or(board_assets.as_deref()) else {
+                        continue;
+                    };
+                    let Some(cover) = board.set_marks(coordinates, *marks) else {
+                        continue;
+                    };
+                    update_flags(
+                        &mut commands,
+                        &board,
+                        (*coordinates, cover),
+                        *marks,
+                        board_assets,
+                        &mut covers,
+                    );
+                    tile_marked_event_wr.send(TileMarkedEvent {
+                        board: entity,
+                        coordinates: *coordinates,
+                        marks: *marks,
+                    });
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Sends the actions played on remote boards to the server instead of playing them locally
+pub fn send_actions(
+    client: Res<NetClient>,
+    boards: Query<(), With<RemoteBoard>>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+) {
+    let actions = tile_trigger_evr
+        .iter()
+        .map(|e| (e.board, Action::Reveal(e.coordinates)))
+        .chain(
+            tile_mark_evr
+                .iter()
+                .map(|e| (e.board, Action::Mark(e.coordinates))),
+        )
+        .chain(
+            tile_chord_evr
+                .iter()
+                .map(|e| (e.board, Action::Chord(e.coordinates))),
+        );
+    for (board, action) in actions {
+        if !boards.contains(board) {
+            continue;
+        }
+        if let Err(e) = client.send(&ClientMessage::Action(action)) {
+            log::error!("Failed to send {:?} to the server, {}", action, e);
+        }
+    }
+}
+
+/// Shares the tile under the mouse cursor with the other players
+pub fn send_cursor(
+    client: Res<NetClient>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&RenderLayers>)>,
+    boards: Query<&Board, With<RemoteBoard>>,
+    mut last_sent: Local<Option<Coordinates>>,
+) {
+    let hovered = cursor_world_position(&windows, &cameras)
+        .and_then(|position| boards.iter().find_map(|b| b.world_to_coordinates(position)));
+    if hovered == *last_sent {
+        return;
+    }
+    *last_sent = hovered;
+    if let Err(e) = client.send(&ClientMessage::Cursor(hovered)) {
+        log::error!("Failed to send cursor to the server, {}", e);
+    }
+}
+
+/// Respawns the cursors of the other players when they move
+pub fn update_remote_cursors(
+    mut commands: Commands,
+    boards: ChangedRemoteBoards,
+    board_assets: Option<Res<BoardAssets>>,
+    cursors: Query<(Entity, &Parent), With<RemoteCursor>>,
+) {
+    for (entity, board, remote, own_assets) in boards.iter() {
+        for (cursor, parent) in cursors.iter() {
+            if parent.get() == entity {
+                commands.entity(cursor).despawn_recursive();
+            }
+        }
+        let Some(board_assets) = own_assets.or(board_assets.as_deref()) else {
+            continue;
+        };
+        commands.entity(entity).with_children(|parent| {
+            for player in remote.players.values() {
+                if let Some(coordinates) = player.cursor {
+                    spawn_remote_cursor(parent, board, player, coordinates, board_assets);
+                }
+            }
+        });
+    }
+}
+
+/// Spawns a tile highlight in the player color, labelled with the player name
+fn spawn_remote_cursor(
+    parent: &mut ChildBuilder,
+    board: &Board,
+    player: &RemotePlayer,
+    coordinates: Coordinates,
+    board_assets: &BoardAssets,
+) {
+    let color = cursor_color(player.id);
+    let translation = board
+        .tile_map
+        .topology()
+        .tile_center(coordinates, board.tile_size)
+        .extend(5.);
+    parent
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(board.tile_size)),
+                color: *color.clone().set_a(0.4),
+                ..Default::default()
+            },
+            transform: Transform::from_translation(translation),
+            ..Default::default()
+        })
+        .insert(Name::new(format!("{} Cursor", player.name)))
+        .insert(RemoteCursor { player: player.id })
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text::from_section(
+                    player.name.clone(),
+                    TextStyle {
+                        font: board_assets.bomb_counter_font.clone(),
+                        font_size: board.tile_size / 2.,
+                        color,
+                    },
+                ),
+                transform: Transform::from_xyz(0., board.tile_size * 0.75, 1.),
+                ..Default::default()
+            });
+        });
+}
+
+/// Distinct cursor color of a player
+fn cursor_color(player: PlayerId) -> Color {
+    // Golden angle hue steps keep consecutive players apart
+    Color::hsl((player as f32 * 137.5) % 360., 0.8, 0.6)
+}
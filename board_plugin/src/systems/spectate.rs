@@ -0,0 +1,169 @@
+use std::sync::mpsc::TryRecvError;
+
+use bevy::{log, prelude::*};
+
+use crate::components::{RemoteBoard, SpectatedBoard};
+use crate::events::{TileChordEvent, TileMarkEvent, TileTriggerEvent};
+use crate::formats::write_layout;
+use crate::net::{SpectatorClient, SpectatorMessage, SpectatorServer};
+use crate::resources::{Action, BoardOptions, BoardSource};
+use crate::systems::input::TileEventWriters;
+use crate::{Board, GHOST_ALPHA};
+
+/// Boards played locally
+type LocalBoards<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static Board, &'static BoardOptions),
+    (Without<RemoteBoard>, Without<SpectatedBoard>),
+>;
+
+/// Spectated boards whose mine visibility changed, or just built
+type ChangedSpectatedBoards<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Board, &'static SpectatedBoard),
+    Or<(Changed<SpectatedBoard>, Added<Board>)>,
+>;
+
+/// Streams the first local board and the actions played on it to the spectators.
+///
+/// Runs after the actions are played, a board being streamed from its current state
+pub fn publish_game(
+    mut server: ResMut<SpectatorServer>,
+    time: Res<Time>,
+    boards: LocalBoards,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+) {
+    server.accept();
+    let now = time.elapsed_seconds();
+    let streamed = server.board().filter(|board| boards.contains(*board));
+    if streamed.is_none() {
+        if let Some((entity, board, options)) = boards.iter().next() {
+            log::info!("Streaming board {:?} to spectators", entity);
+            let options = BoardOptions {
+                source: BoardSource::Layout(write_layout(&board.tile_map)),
+                seed: None,
+                safe_start: false,
+                players: Vec::new(),
+                ..options.clone()
+            };
+            server.start(entity, options, now);
+        }
+    }
+    let actions = tile_trigger_evr
+        .iter()
+        .map(|e| (e.board, Action::Reveal(e.coordinates)))
+        .chain(
+            tile_mark_evr
+                .iter()
+                .map(|e| (e.board, Action::Mark(e.coordinates))),
+        )
+        .chain(
+            tile_chord_evr
+                .iter()
+                .map(|e| (e.board, Action::Chord(e.coordinates))),
+        );
+    for (board, action) in actions {
+        // The actions played before the stream started are part of its initial state
+        if streamed == Some(board) {
+            server.publish(action, now);
+        }
+    }
+}
+
+/// Spawns the streamed board and replays the streamed actions on it.
+///
+/// Messages following a new board are held until it is built
+pub fn receive_spectated_game(
+    mut commands: Commands,
+    client: Res<SpectatorClient>,
+    board_options: Option<Res<BoardOptions>>,
+    boards: Query<(Entity, &SpectatedBoard, Option<&Board>)>,
+    (mut trigger_ewr, mut mark_ewr, mut chord_ewr): TileEventWriters,
+) {
+    if boards.iter().any(|(_, _, board)| board.is_none()) {
+        return;
+    }
+    loop {
+        let message = match client.try_recv() {
+            Ok(message) => message,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                log::error!("The spectated game ended");
+                commands.remove_resource::<SpectatorClient>();
+                return;
+            }
+        };
+        match message {
+            SpectatorMessage::Board(options) => {
+                let mut spectated = SpectatedBoard::default();
+                for (entity, previous, _) in boards.iter() {
+                    spectated = *previous;
+                    commands.entity(entity).despawn_recursive();
+                }
+                let local = board_options.as_deref().cloned().unwrap_or_default();
+                commands
+                    .spawn(BoardOptions {
+                        position: local.position,
+                        tile_size: local.tile_size,
+                        tile_padding: local.tile_padding,
+                        reveal_animation: local.reveal_animation,
+                        ..options
+                    })
+                    .insert(spectated);
+                return;
+            }
+            SpectatorMessage::Action(timed) => {
+                let Some((board, ..)) = boards.iter().next() else {
+                    continue;
+                };
+                match timed.action {
+                    Action::Reveal(coordinates) => {
+                        trigger_ewr.send(TileTriggerEvent { board, coordinates })
+                    }
+                    Action::Mark(coordinates) => {
+                        mark_ewr.send(TileMarkEvent { board, coordinates })
+                    }
+                    Action::Chord(coordinates) => {
+                        chord_ewr.send(TileChordEvent { board, coordinates })
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Makes the covers of the spectated mines translucent when requested
+pub fn show_spectated_mines(
+    boards: ChangedSpectatedBoards,
+    mut sprites: Query<AnyOf<(&mut Sprite, &mut TextureAtlasSprite)>>,
+) {
+    const SHOWN_ALPHA: f32 = 0.3;
+    for (board, spectated) in boards.iter() {
+        let (alpha, ghost_alpha) = match spectated.show_mines {
+            true => (SHOWN_ALPHA, SHOWN_ALPHA * GHOST_ALPHA),
+            false => (1., GHOST_ALPHA),
+        };
+        for (coordinates, cover) in board.covered_tiles.iter() {
+            if !board.tile_map.is_bomb_at(*coordinates) {
+                continue;
+            }
+            let covers = std::iter::once((cover, alpha)).chain(
+                board
+                    .ghost_covers(coordinates)
+                    .iter()
+                    .map(|g| (g, ghost_alpha)),
+            );
+            for (entity, alpha) in covers {
+                match sprites.get_mut(*entity) {
+                    Ok((Some(mut sprite), _)) => sprite.color.set_a(alpha),
+                    Ok((_, Some(mut sprite))) => sprite.color.set_a(alpha),
+                    _ => continue,
+                };
+            }
+        }
+    }
+}
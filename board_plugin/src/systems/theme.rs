@@ -0,0 +1,62 @@
+use bevy::{log, prelude::*};
+
+use crate::formats::write_layout;
+use crate::resources::{BoardAssets, BoardOptions, BoardPalette, BoardSource, BoardTheme, Theme};
+use crate::Board;
+
+/// Built boards using the [`BoardAssets`] resource
+type ThemedBoards<'w, 's> =
+    Query<'w, 's, (Entity, &'static Board, &'static mut BoardOptions), Without<BoardAssets>>;
+
+/// Applies the [`BoardTheme`] once loaded, and again whenever it changes, its file is reloaded
+/// or the [`BoardPalette`] changes.
+///
+/// The built boards are rebuilt from their current state with the new theme
+pub fn apply_theme(
+    mut commands: Commands,
+    (board_theme, palette): (Res<BoardTheme>, Option<Res<BoardPalette>>),
+    themes: Res<Assets<Theme>>,
+    mut theme_evr: EventReader<AssetEvent<Theme>>,
+    board_options: Option<ResMut<BoardOptions>>,
+    mut boards: ThemedBoards,
+) {
+    let loaded = theme_evr.iter().any(|event| match event {
+        AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+            *handle == board_theme.theme
+        }
+        AssetEvent::Removed { .. } => false,
+    });
+    if !loaded && !board_theme.is_changed() && !palette.as_ref().is_some_and(|p| p.is_changed()) {
+        return;
+    }
+    // Applied by the loading event otherwise
+    let Some(theme) = themes.get(&board_theme.theme) else {
+        return;
+    };
+    let tile_padding = board_theme.tile_padding.unwrap_or(theme.tile_padding);
+    log::info!("Applying the {} theme", theme.assets.label);
+    let mut assets = theme.assets.clone();
+    if let Some(palette) = palette {
+        let colors = palette.palette.colors();
+        // Skins can't recolor or outline their own counters
+        if let Some(skin) = &mut assets.skin {
+            skin.numbers &= colors.is_none() && !palette.high_contrast;
+        }
+        assets.bomb_counter_colors = colors.unwrap_or(assets.bomb_counter_colors);
+        assets.high_contrast = palette.high_contrast;
+    }
+    commands.insert_resource(assets);
+    if let Some(mut options) = board_options {
+        options.tile_padding = tile_padding;
+    }
+    for (entity, board, mut options) in boards.iter_mut() {
+        options.source = BoardSource::Layout(write_layout(&board.tile_map));
+        options.seed = None;
+        options.safe_start = false;
+        options.tile_padding = tile_padding;
+        commands
+            .entity(entity)
+            .remove::<Board>()
+            .despawn_descendants();
+    }
+}
@@ -1,61 +1,162 @@
-use crate::events::{BoardCompletedEvent, BombExplosionEvent, TileTriggerEvent};
-use crate::{Board, Bomb, BombNeighbor, Coordinates, Uncover};
+use crate::components::RemoteBoard;
+use crate::events::{
+    BoardCompletedEvent, BombExplosionEvent, TileChordEvent, TileTriggerEvent, TilesRevealedEvent,
+};
+use crate::resources::{BoardOptions, RevealAnimation, TileMap};
+use crate::{Board, Coordinates, Uncover, UncoverDelay};
+use bevy::utils::HashSet;
 use bevy::{log, prelude::*};
 
+/// Writers of the events following a reveal, see [`uncover_revealed`]
+pub(crate) type RevealEventWriters<'w> = (
+    EventWriter<'w, TilesRevealedEvent>,
+    EventWriter<'w, BoardCompletedEvent>,
+    EventWriter<'w, BombExplosionEvent>,
+);
+
 pub fn trigger_event_handler(
     mut commands: Commands,
-    board: Res<Board>,
+    mut boards: Query<(&mut Board, &BoardOptions), Without<RemoteBoard>>,
     mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut reveal_event_wrs: RevealEventWriters,
 ) {
     for trigger_event in tile_trigger_evr.iter() {
-        // If the tile is covered, uncover it
-        if let Some(entity) = board.tile_to_uncover(&trigger_event.0) {
-            commands.entity(*entity).insert(Uncover);
+        let Ok((mut board, options)) = boards.get_mut(trigger_event.board) else {
+            continue;
+        };
+        // Exploded and completed boards are over
+        if board.is_exploded() || board.is_completed() {
+            continue;
         }
+        // The whole empty region is revealed at once
+        let revealed = board.reveal(&trigger_event.coordinates);
+        uncover_revealed(
+            &mut commands,
+            (trigger_event.board, &board),
+            trigger_event.coordinates,
+            revealed,
+            options.reveal_animation.as_ref(),
+            &mut reveal_event_wrs,
+        );
     }
 }
 
-pub fn uncover_tiles(
+pub fn chord_event_handler(
     mut commands: Commands,
-    board: Option<ResMut<Board>>,
-    children: Query<(Entity, &Parent), With<Uncover>>,
-    parents: Query<(&Coordinates, Option<&Bomb>, Option<&BombNeighbor>)>,
-    mut board_completed_event_wr: EventWriter<BoardCompletedEvent>,
-    mut bomb_explosion_event_wr: EventWriter<BombExplosionEvent>,
+    mut boards: Query<(&mut Board, &BoardOptions), Without<RemoteBoard>>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+    mut reveal_event_wrs: RevealEventWriters,
 ) {
-    if let Some(mut board) = board {
-        for (entity, parent) in children.iter() {
-            commands.entity(entity).despawn_recursive();
-            let (coords, bomb, bomb_counter) = match parents.get(parent.get()) {
-                Ok(v) => v,
-                Err(e) => {
-                    log::error!("{}", e);
-                    continue;
-                }
-            };
-            log::debug!("Uncovering tile {}", coords);
-            // We remove the entity from the board covered tile map
-            match board.try_uncover_tile(coords) {
-                None => log::trace!("Tried to uncover an already uncovered tile"),
-                Some(e) => log::trace!("Uncovered tile {} (entity: {:?})", coords, e),
-            }
-            if board.is_completed() {
-                log::info!("Board completed !");
-                board_completed_event_wr.send(BoardCompletedEvent);
-            }
-            if bomb.is_some() {
-                log::info!("Boom !");
-                bomb_explosion_event_wr.send(BombExplosionEvent);
-            }
-            // If the tile is empty..
-            else if bomb_counter.is_none() {
-                // .. We propagate the uncovering by adding the `Uncover` component to adjacent tiles
-                // which will then be removed next frame
-                for entity in board.adjacent_covered_tiles(*coords) {
-                    log::trace!("Adding uncover to {}", coords);
-                    commands.entity(entity).insert(Uncover);
-                }
-            }
+    for chord_event in tile_chord_evr.iter() {
+        let Ok((mut board, options)) = boards.get_mut(chord_event.board) else {
+            continue;
+        };
+        if board.is_exploded() || board.is_completed() {
+            continue;
         }
+        let revealed = board.chord(&chord_event.coordinates);
+        uncover_revealed(
+            &mut commands,
+            (chord_event.board, &board),
+            chord_event.coordinates,
+            revealed,
+            options.reveal_animation.as_ref(),
+            &mut reveal_event_wrs,
+        );
+    }
+}
+
+/// Checks the board state after a reveal from `origin` and uncovers the revealed covers
+pub(crate) fn uncover_revealed(
+    commands: &mut Commands,
+    (entity, board): (Entity, &Board),
+    origin: Coordinates,
+    revealed: Vec<(Coordinates, Entity)>,
+    animation: Option<&RevealAnimation>,
+    (tiles_revealed_event_wr, board_completed_event_wr, bomb_explosion_event_wr): &mut RevealEventWriters,
+) {
+    if revealed.is_empty() {
+        return;
+    }
+    // Tiles on the edges of wrapping boards have ghost covers too
+    let count = revealed
+        .iter()
+        .map(|(c, _)| c)
+        .collect::<HashSet<_>>()
+        .len();
+    log::debug!("Uncovering {} tiles from {}", count, origin);
+    tiles_revealed_event_wr.send(TilesRevealedEvent {
+        board: entity,
+        count,
+    });
+    if revealed.iter().any(|(c, _)| board.tile_map.is_bomb_at(*c)) {
+        log::info!("Boom !");
+        bomb_explosion_event_wr.send(BombExplosionEvent { board: entity });
+    } else if board.is_completed() {
+        log::info!("Board completed !");
+        board_completed_event_wr.send(BoardCompletedEvent { board: entity });
+    }
+    for (coords, entity) in revealed {
+        let delay = animation.map_or(0., |a| wave_delay(a, &board.tile_map, origin, coords));
+        if delay > 0. {
+            commands
+                .entity(entity)
+                .insert(UncoverDelay(Timer::from_seconds(delay, TimerMode::Once)));
+        } else {
+            commands.entity(entity).insert(Uncover);
+        }
+    }
+}
+
+/// Delay before uncovering `coords` in a wave starting at `origin`
+fn wave_delay(
+    animation: &RevealAnimation,
+    tile_map: &TileMap,
+    origin: Coordinates,
+    coords: Coordinates,
+) -> f32 {
+    let distance = wave_distance(tile_map, origin, coords);
+    (distance as f32 * animation.tile_delay).min(animation.max_duration)
+}
+
+/// Ring of `coords` in a wave starting at `origin`
+pub(crate) fn wave_distance(tile_map: &TileMap, origin: Coordinates, coords: Coordinates) -> u16 {
+    let origin = (origin.x as i32, origin.y as i32);
+    // The wave may spread across the edges, reaching the nearest copy of the tile
+    let copies = match tile_map.is_wrapping() {
+        true => -1..=1,
+        false => 0..=0,
+    };
+    let (width, height) = (tile_map.width() as i32, tile_map.height() as i32);
+    copies
+        .clone()
+        .flat_map(|i| copies.clone().map(move |j| (i, j)))
+        .map(|(i, j)| {
+            let copy = (coords.x as i32 + i * width, coords.y as i32 + j * height);
+            tile_map.topology().distance(origin, copy)
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+pub fn tick_uncover_delays(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut delays: Query<(Entity, &mut UncoverDelay)>,
+) {
+    for (entity, mut delay) in delays.iter_mut() {
+        if delay.0.tick(time.delta()).finished() {
+            commands
+                .entity(entity)
+                .remove::<UncoverDelay>()
+                .insert(Uncover);
+        }
+    }
+}
+
+pub fn uncover_tiles(mut commands: Commands, covers: Query<Entity, With<Uncover>>) {
+    for entity in covers.iter() {
+        log::trace!("Removing tile cover {:?}", entity);
+        commands.entity(entity).despawn_recursive();
     }
 }
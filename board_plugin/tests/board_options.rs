@@ -0,0 +1,52 @@
+use board_plugin::formats::LayoutErrorKind;
+use board_plugin::resources::{BoardOptions, BoardOptionsError, BoardRng, BoardSource, Topology};
+use rand::SeedableRng;
+
+#[test]
+fn rejects_odd_wrapping_hexagonal_boards() {
+    let options = BoardOptions {
+        map_size: (8, 7),
+        bomb_count: 5,
+        topology: Topology::Hexagonal,
+        wrapping: true,
+        ..Default::default()
+    };
+    let mut rng = BoardRng::seed_from_u64(1);
+    assert_eq!(
+        options.generate_tile_map(&mut rng).unwrap_err(),
+        BoardOptionsError::OddWrappingHexHeight(7)
+    );
+    let even = BoardOptions {
+        map_size: (8, 8),
+        ..options
+    };
+    assert!(even.generate_tile_map(&mut rng).is_ok());
+}
+
+#[test]
+fn measures_hexagonal_distances_in_steps() {
+    let hex = Topology::Hexagonal;
+    // Both upper neighbors of an even row tile are one step away
+    assert_eq!(hex.distance((2, 2), (1, 3)), 1);
+    assert_eq!(hex.distance((2, 2), (2, 3)), 1);
+    assert_eq!(hex.distance((2, 2), (3, 3)), 2);
+    // Two rows up, the row shift cancels out
+    assert_eq!(hex.distance((2, 2), (2, 4)), 2);
+    assert_eq!(hex.distance((2, 2), (5, 2)), 3);
+    assert_eq!(Topology::Square.distance((2, 2), (3, 4)), 2);
+}
+
+#[test]
+fn rejects_invalid_layouts() {
+    let options = BoardOptions {
+        source: BoardSource::Layout("..*\n.?.\n".into()),
+        ..Default::default()
+    };
+    let mut rng = BoardRng::seed_from_u64(1);
+    // A typo doesn't turn the puzzle into a random board
+    let Err(BoardOptionsError::Layout(e)) = options.generate_tile_map(&mut rng) else {
+        panic!("The layout is invalid");
+    };
+    assert_eq!((e.line, e.column), (2, 2));
+    assert_eq!(e.kind, LayoutErrorKind::InvalidTile("?".into()));
+}
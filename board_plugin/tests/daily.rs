@@ -0,0 +1,93 @@
+use board_plugin::daily::{AttemptKind, AttemptResult, DailyChallenge, DailyStats, Date};
+use board_plugin::resources::Difficulty;
+use board_plugin::simulation::Outcome;
+
+fn date(year: i32, month: u8, day: u8) -> Date {
+    Date { year, month, day }
+}
+
+#[test]
+fn converts_dates() {
+    assert_eq!(Date::from_days(0), date(1970, 1, 1));
+    assert_eq!(Date::from_days(11_016), date(2000, 2, 29));
+    assert_eq!(Date::from_days(-1), date(1969, 12, 31));
+    let leap_day = date(2024, 2, 29);
+    assert_eq!(Date::from_days(leap_day.days()), leap_day);
+    assert_eq!(leap_day.to_string(), "2024-02-29");
+}
+
+#[test]
+fn derives_seeds_from_date_and_difficulty() {
+    let challenge = |date, difficulty| DailyChallenge { date, difficulty };
+    let expert = challenge(date(2024, 3, 1), Difficulty::Expert);
+    assert_eq!(expert.seed(), expert.seed());
+    assert_ne!(
+        expert.seed(),
+        challenge(date(2024, 3, 1), Difficulty::Beginner).seed()
+    );
+    assert_ne!(
+        expert.seed(),
+        challenge(date(2024, 3, 2), Difficulty::Expert).seed()
+    );
+    let options = expert.board_options(Default::default());
+    assert_eq!((options.map_size, options.bomb_count), ((30, 16), 99));
+}
+
+#[test]
+fn records_the_first_attempt_as_official() {
+    let challenge = DailyChallenge {
+        date: date(2024, 3, 1),
+        difficulty: Difficulty::Beginner,
+    };
+    let mut stats = DailyStats::default();
+    let result = |outcome, time| AttemptResult {
+        outcome,
+        time,
+        three_bv: 12,
+    };
+    assert_eq!(stats.start_attempt(&challenge), AttemptKind::Official);
+    assert_eq!(
+        stats.record(&challenge).unwrap().official.outcome,
+        Outcome::Abandoned
+    );
+    stats.finish_attempt(&challenge, AttemptKind::Official, result(Outcome::Lost, 8.));
+
+    assert_eq!(stats.start_attempt(&challenge), AttemptKind::Practice);
+    stats.finish_attempt(&challenge, AttemptKind::Practice, result(Outcome::Won, 20.));
+    assert_eq!(stats.start_attempt(&challenge), AttemptKind::Practice);
+    stats.finish_attempt(&challenge, AttemptKind::Practice, result(Outcome::Won, 15.));
+
+    let record = stats.record(&challenge).unwrap();
+    assert_eq!(record.official, result(Outcome::Lost, 8.));
+    assert_eq!(record.practice_attempts, 2);
+    assert_eq!(record.best_practice_time, Some(15.));
+}
+
+#[test]
+fn pins_the_daily_boards() {
+    let challenge = DailyChallenge {
+        date: date(2024, 3, 1),
+        difficulty: Difficulty::Beginner,
+    };
+    let options = challenge.board_options(Default::default());
+    let tile_map = options.generate_tile_map(&mut options.rng()).unwrap();
+    let mines: Vec<(u16, u16)> = tile_map
+        .iter()
+        .filter(|(_, tile)| tile.is_bomb())
+        .map(|(c, _)| (c.x, c.y))
+        .collect();
+    // Every player gets the same board, whatever their platform
+    let expected = [
+        (5, 1),
+        (2, 4),
+        (8, 4),
+        (2, 5),
+        (5, 5),
+        (7, 5),
+        (0, 6),
+        (5, 6),
+        (4, 7),
+        (4, 8),
+    ];
+    assert_eq!(mines, expected);
+}
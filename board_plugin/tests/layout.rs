@@ -0,0 +1,38 @@
+use board_plugin::formats::{parse_layout, LayoutError, LayoutErrorKind};
+
+fn error(text: &str) -> LayoutError {
+    parse_layout(text).unwrap_err()
+}
+
+#[test]
+fn reports_unknown_tiles() {
+    let e = error("# A puzzle\n..*\n.?.\n");
+    assert_eq!((e.line, e.column), (3, 2));
+    assert_eq!(e.kind, LayoutErrorKind::InvalidTile("?".into()));
+    // Tokens are located by their first character
+    let e = error("o . *\n. ?? .\n");
+    assert_eq!((e.line, e.column), (2, 3));
+    assert_eq!(e.kind, LayoutErrorKind::InvalidTile("??".into()));
+    assert_eq!(e.to_string(), "line 2, column 3: invalid tile `??`");
+}
+
+#[test]
+fn reports_ragged_rows() {
+    let e = error("...\n..\n");
+    assert_eq!((e.line, e.column), (2, 3));
+    assert_eq!(
+        e.kind,
+        LayoutErrorKind::RowLength {
+            expected: 3,
+            found: 2
+        }
+    );
+}
+
+#[test]
+fn reports_empty_layouts() {
+    let e = error("");
+    assert_eq!((e.line, e.column, e.kind), (1, 1, LayoutErrorKind::Empty));
+    let e = error("# Nothing yet\n\n");
+    assert_eq!((e.line, e.column, e.kind), (2, 1, LayoutErrorKind::Empty));
+}
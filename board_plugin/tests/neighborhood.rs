@@ -0,0 +1,81 @@
+use board_plugin::components::Coordinates;
+use board_plugin::resources::{
+    BoardOptions, BoardOptionsError, BoardRng, Neighborhood, TileMap, Topology,
+};
+use rand::SeedableRng;
+
+/// Every delta of an 11x11 square, far more than a custom neighborhood keeps
+fn large_neighborhood() -> Neighborhood {
+    Neighborhood::Custom(
+        (-5..=5)
+            .flat_map(|dy| (-5..=5).map(move |dx| (dx, dy)))
+            .collect(),
+    )
+}
+
+#[test]
+fn bounds_custom_neighbor_counts() {
+    let mut tile_map = TileMap::empty(11, 11)
+        .with_neighborhood(large_neighborhood())
+        .with_max_mines_per_tile(TileMap::MAX_MINES_PER_TILE);
+    let center = Coordinates { x: 5, y: 5 };
+    for y in 0..11 {
+        for x in 0..11 {
+            let coordinates = Coordinates { x, y };
+            if coordinates != center {
+                tile_map.set_mines_at(coordinates, TileMap::MAX_MINES_PER_TILE);
+            }
+        }
+    }
+    tile_map.refresh_bomb_counts();
+    assert_eq!(tile_map.bomb_count_at(center), u8::MAX);
+}
+
+#[test]
+fn rejects_oversized_custom_neighborhoods() {
+    let options = BoardOptions {
+        neighborhood: large_neighborhood(),
+        ..Default::default()
+    };
+    let mut rng = BoardRng::seed_from_u64(1);
+    assert_eq!(
+        options.generate_tile_map(&mut rng).unwrap_err(),
+        BoardOptionsError::TooManyNeighbors
+    );
+}
+
+/// Neighbors of every tile of a wrapping map, asserting none is listed twice
+fn wrapped_neighbors(tile_map: &TileMap) -> Vec<Vec<Coordinates>> {
+    let mut all = Vec::new();
+    for y in 0..tile_map.height() {
+        for x in 0..tile_map.width() {
+            let coordinates = Coordinates { x, y };
+            let mut neighbors: Vec<_> = tile_map.safe_square_at(coordinates).collect();
+            assert!(!neighbors.contains(&coordinates));
+            neighbors.sort_by_key(|c| (c.y, c.x));
+            let count = neighbors.len();
+            neighbors.dedup();
+            assert_eq!(
+                neighbors.len(),
+                count,
+                "{} has duplicated neighbors",
+                coordinates
+            );
+            all.push(neighbors);
+        }
+    }
+    all
+}
+
+#[test]
+fn wraps_small_moore_maps() {
+    // Every other tile of a 3x3 map is a neighbor exactly once
+    let tile_map = TileMap::empty(3, 3).with_wrapping(true);
+    assert!(wrapped_neighbors(&tile_map).iter().all(|n| n.len() == 8));
+    let tile_map = TileMap::empty(2, 2).with_wrapping(true);
+    assert!(wrapped_neighbors(&tile_map).iter().all(|n| n.len() == 3));
+    let tile_map = TileMap::empty(3, 4)
+        .with_topology(Topology::Hexagonal)
+        .with_wrapping(true);
+    assert!(wrapped_neighbors(&tile_map).iter().all(|n| n.len() == 6));
+}
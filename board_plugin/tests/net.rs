@@ -0,0 +1,262 @@
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use bevy::prelude::Entity;
+use board_plugin::components::Coordinates;
+use board_plugin::net::{
+    read_message, write_message, ClientMessage, GameServer, NetMode, PlayerState, ServerMessage,
+    SpectatorMessage, SpectatorServer,
+};
+use board_plugin::resources::{Action, BoardOptions, BoardSource, TimedAction};
+
+/// Raw protocol client
+struct Client {
+    writer: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl Client {
+    fn join(address: &str, name: &str) -> Self {
+        let writer = TcpStream::connect(address).unwrap();
+        let reader = BufReader::new(writer.try_clone().unwrap());
+        let mut client = Self { writer, reader };
+        client.send(ClientMessage::Join {
+            name: name.to_string(),
+        });
+        client
+    }
+
+    fn send(&mut self, message: ClientMessage) {
+        write_message(&mut self.writer, &message).unwrap();
+    }
+
+    fn recv(&mut self) -> ServerMessage {
+        read_message(&mut self.reader).unwrap().unwrap()
+    }
+}
+
+fn at(x: u16, y: u16) -> Coordinates {
+    Coordinates { x, y }
+}
+
+fn start_server(layout: &str, mode: NetMode) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let server = GameServer::new(BoardOptions {
+        source: BoardSource::Layout(layout.to_string()),
+        ..Default::default()
+    })
+    .unwrap()
+    .with_mode(mode);
+    thread::spawn(move || server.run(listener));
+    address
+}
+
+#[test]
+fn shares_the_board_between_players() {
+    let address = start_server("..*\n...\n...\n", NetMode::Coop);
+    let mut alice = Client::join(&address, "Alice");
+    let ServerMessage::Welcome {
+        player: alice_id,
+        players,
+        ..
+    } = alice.recv()
+    else {
+        panic!("expected a welcome");
+    };
+    assert!(players.is_empty());
+
+    let mut bob = Client::join(&address, "Bob");
+    let ServerMessage::Welcome { players, .. } = bob.recv() else {
+        panic!("expected a welcome");
+    };
+    assert_eq!(players.len(), 1);
+    assert_eq!(players[0].name, "Alice");
+    assert!(matches!(alice.recv(), ServerMessage::Joined(p) if p.name == "Bob"));
+
+    // Cursors are only sent to the other players
+    alice.send(ClientMessage::Cursor(Some(at(1, 1))));
+    let ServerMessage::Cursor {
+        player,
+        coordinates,
+    } = bob.recv()
+    else {
+        panic!("expected a cursor");
+    };
+    assert_eq!((player, coordinates), (alice_id, Some(at(1, 1))));
+
+    bob.send(ClientMessage::Action(Action::Mark(at(2, 2))));
+    for client in [&mut alice, &mut bob] {
+        let ServerMessage::Marked {
+            coordinates, marks, ..
+        } = client.recv()
+        else {
+            panic!("expected a mark");
+        };
+        assert_eq!((coordinates, marks), (at(2, 2), 1));
+    }
+
+    alice.send(ClientMessage::Action(Action::Reveal(at(0, 0))));
+    for client in [&mut alice, &mut bob] {
+        let ServerMessage::Revealed { player, tiles } = client.recv() else {
+            panic!("expected revealed tiles");
+        };
+        assert_eq!(player, alice_id);
+        assert_eq!(tiles.len(), 8);
+    }
+
+    // Late players join the current board state
+    let mut carol = Client::join(&address, "Carol");
+    let ServerMessage::Welcome { options, .. } = carol.recv() else {
+        panic!("expected a welcome");
+    };
+    let BoardSource::Layout(layout) = options.source else {
+        panic!("expected a layout");
+    };
+    assert_eq!(layout, "ooF\nooo\nooo\n");
+
+    drop(bob);
+    assert!(matches!(alice.recv(), ServerMessage::Joined(p) if p.name == "Carol"));
+    assert!(matches!(alice.recv(), ServerMessage::Left(_)));
+}
+
+#[test]
+fn ranks_versus_players() {
+    let address = start_server("..*\n...\n...\n", NetMode::Versus);
+    let mut alice = Client::join(&address, "Alice");
+    let ServerMessage::Welcome {
+        player: alice_id,
+        mode,
+        options: alice_options,
+        ..
+    } = alice.recv()
+    else {
+        panic!("expected a welcome");
+    };
+    assert_eq!(mode, NetMode::Versus);
+    assert!(matches!(alice.recv(), ServerMessage::Progress(p) if p.cleared == 0.));
+
+    let mut bob = Client::join(&address, "Bob");
+    let ServerMessage::Welcome {
+        player: bob_id,
+        options: bob_options,
+        ..
+    } = bob.recv()
+    else {
+        panic!("expected a welcome");
+    };
+    // Every player gets the same board
+    assert!(alice_options.seed.is_some());
+    assert_eq!(alice_options.seed, bob_options.seed);
+    assert_eq!(
+        format!("{:?}", alice_options.source),
+        format!("{:?}", bob_options.source)
+    );
+    assert!(matches!(bob.recv(), ServerMessage::Progress(p) if p.player == bob_id));
+    assert!(matches!(bob.recv(), ServerMessage::Progress(p) if p.player == alice_id));
+    assert!(matches!(alice.recv(), ServerMessage::Joined(p) if p.name == "Bob"));
+    assert!(matches!(alice.recv(), ServerMessage::Progress(p) if p.player == bob_id));
+
+    // Boards are played separately, only progress is shared
+    alice.send(ClientMessage::Action(Action::Reveal(at(0, 0))));
+    assert!(matches!(alice.recv(), ServerMessage::Revealed { tiles, .. } if tiles.len() == 8));
+    for client in [&mut alice, &mut bob] {
+        let ServerMessage::Progress(progress) = client.recv() else {
+            panic!("expected a progress");
+        };
+        assert_eq!(
+            (progress.player, progress.state),
+            (alice_id, PlayerState::Won)
+        );
+        assert_eq!(progress.cleared, 1.);
+        assert!(progress.time.is_some() && progress.three_bv_per_second.is_some());
+    }
+
+    bob.send(ClientMessage::Action(Action::Mark(at(0, 0))));
+    assert!(matches!(bob.recv(), ServerMessage::Marked { marks: 1, .. }));
+    for client in [&mut alice, &mut bob] {
+        assert!(matches!(client.recv(), ServerMessage::Progress(p) if p.flags == 1));
+    }
+    bob.send(ClientMessage::Action(Action::Reveal(at(2, 2))));
+    assert!(matches!(bob.recv(), ServerMessage::Revealed { .. }));
+    for client in [&mut alice, &mut bob] {
+        let ServerMessage::Progress(progress) = client.recv() else {
+            panic!("expected a progress");
+        };
+        assert_eq!(
+            (progress.player, progress.state),
+            (bob_id, PlayerState::Lost)
+        );
+        let ServerMessage::Ranking(ranking) = client.recv() else {
+            panic!("expected the ranking");
+        };
+        let ranking: Vec<_> = ranking.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(ranking, ["Alice", "Bob"]);
+    }
+
+    // Late players get the final ranking and wait for the next round
+    let mut carol = Client::join(&address, "Carol");
+    assert!(matches!(carol.recv(), ServerMessage::Welcome { .. }));
+    assert!(matches!(carol.recv(), ServerMessage::Progress(p) if p.player == alice_id));
+    assert!(matches!(carol.recv(), ServerMessage::Progress(p) if p.player == bob_id));
+    let ServerMessage::Ranking(ranking) = carol.recv() else {
+        panic!("expected the ranking");
+    };
+    assert_eq!(ranking.len(), 2);
+    assert!(matches!(alice.recv(), ServerMessage::Joined(p) if p.name == "Carol"));
+    carol.send(ClientMessage::Action(Action::Reveal(at(0, 0))));
+    carol.send(ClientMessage::Restart);
+    let ServerMessage::Welcome { player, .. } = carol.recv() else {
+        panic!("expected the actions to be ignored until the restart");
+    };
+    let carol_id = player;
+    let mut progress = Vec::new();
+    while progress.len() < 3 {
+        match carol.recv() {
+            ServerMessage::Progress(p) => progress.push((p.player, p.state)),
+            message => panic!("unexpected {:?}", message),
+        }
+    }
+    assert!(progress.contains(&(carol_id, PlayerState::Playing)));
+}
+
+#[test]
+fn streams_the_game_to_late_spectators() {
+    let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+    let address = server.local_addr().unwrap();
+    // Actions played before a board is streamed are dropped
+    server.publish(Action::Reveal(at(2, 2)), 0.5);
+    let options = BoardOptions {
+        source: BoardSource::Layout("..*\n...\n...\n".to_string()),
+        ..Default::default()
+    };
+    server.start(Entity::from_raw(0), options, 1.);
+    server.publish(Action::Reveal(at(0, 0)), 1.5);
+    server.publish(Action::Mark(at(2, 2)), 3.);
+
+    let stream = TcpStream::connect(address).unwrap();
+    let mut reader = BufReader::new(stream);
+    server.accept();
+    server.publish(Action::Chord(at(1, 1)), 4.);
+    let mut recv = || {
+        read_message::<SpectatorMessage>(&mut reader)
+            .unwrap()
+            .unwrap()
+    };
+    let SpectatorMessage::Board(options) = recv() else {
+        panic!("expected the board first");
+    };
+    assert!(matches!(options.source, BoardSource::Layout(layout) if layout.contains('*')));
+    let expected = [
+        (0.5, Action::Reveal(at(0, 0))),
+        (2., Action::Mark(at(2, 2))),
+        (3., Action::Chord(at(1, 1))),
+    ];
+    for (time, action) in expected {
+        let SpectatorMessage::Action(timed) = recv() else {
+            panic!("expected {:?}", action);
+        };
+        assert_eq!(timed, TimedAction { time, action });
+    }
+}
@@ -0,0 +1,133 @@
+use board_plugin::components::Coordinates;
+use board_plugin::formats::{parse_layout, parse_rawvf, write_layout, write_rawvf, RawVfErrorKind};
+use board_plugin::resources::{Action, Replay, TimedAction};
+
+const ARBITER_FILE: &str = "RawVF_Version: Rev5
+Program: Minesweeper Arbiter
+Version: 0.52.3
+Player: Anonymous
+Level: Custom
+Width: 4
+Height: 3
+Mines: 2
+Marks: Off
+Time: 1.20
+Board:
+*000
+0000
+000*
+Events:
+0.00 start
+0.00 lc 1 3 (8 40)
+0.05 lr 1 3 (8 40)
+0.30 mv 2 3 (24 40)
+0.40 rc 1 1 (8 8)
+0.45 rr 1 1 (8 8)
+0.80 lc 2 2 (24 24)
+0.82 rc 2 2 (24 24)
+0.90 lr 2 2 (24 24)
+0.95 rr 2 2 (24 24)
+1.20 mr 3 3 (40 40)
+1.20 won
+";
+
+fn at(x: u16, y: u16) -> Coordinates {
+    Coordinates { x, y }
+}
+
+#[test]
+fn parses_arbiter_file() {
+    let replay = parse_rawvf(ARBITER_FILE).unwrap();
+    assert_eq!(write_layout(&replay.tile_map), "*...\n....\n...*\n");
+    let actions: Vec<Action> = replay.actions.iter().map(|a| a.action).collect();
+    assert_eq!(
+        actions,
+        [
+            Action::Reveal(at(0, 0)),
+            Action::Mark(at(0, 2)),
+            Action::Chord(at(1, 1)),
+            Action::Chord(at(2, 0)),
+        ]
+    );
+    assert_eq!(replay.actions[2].time, 0.9);
+}
+
+#[test]
+fn replays_actions() {
+    let replay = parse_rawvf(ARBITER_FILE).unwrap();
+    let tile_map = replay.play();
+    assert_eq!(write_layout(&tile_map), "Fooo\noooo\nooo*\n");
+}
+
+#[test]
+fn round_trips_replays() {
+    let tile_map = parse_layout("..*.o\n*....\n.....\n").unwrap();
+    let actions = vec![
+        TimedAction {
+            time: 0.,
+            action: Action::Reveal(at(4, 0)),
+        },
+        TimedAction {
+            time: 1.25,
+            action: Action::Mark(at(2, 2)),
+        },
+        TimedAction {
+            time: 2.5,
+            action: Action::Chord(at(1, 1)),
+        },
+        TimedAction {
+            time: 3.75,
+            action: Action::Reveal(at(0, 2)),
+        },
+    ];
+    let replay = Replay { tile_map, actions };
+    let text = write_rawvf(&replay);
+    let parsed = parse_rawvf(&text).unwrap();
+    // RAWVF boards start fully covered
+    assert_eq!(write_layout(&parsed.tile_map), "..*..\n*....\n.....\n");
+    assert_eq!(parsed.actions, replay.actions);
+    assert_eq!(write_rawvf(&parsed), text);
+}
+
+#[test]
+fn skips_actions_outside_the_board() {
+    let tile_map = parse_layout("..*\n...\n").unwrap();
+    let reveal = TimedAction {
+        time: 0.5,
+        action: Action::Reveal(at(0, 0)),
+    };
+    let outside = [at(3, 0), at(0, 2), at(u16::MAX, u16::MAX)].map(|c| TimedAction {
+        time: 1.,
+        action: Action::Mark(c),
+    });
+    let replay = Replay {
+        tile_map,
+        actions: [reveal].into_iter().chain(outside).collect(),
+    };
+    let parsed = parse_rawvf(&write_rawvf(&replay)).unwrap();
+    assert_eq!(parsed.actions, vec![reveal]);
+}
+
+#[test]
+fn reports_errors() {
+    let error = parse_rawvf("Width: 2\nHeight: 1\nMines: 1\nBoard:\n*?\n").unwrap_err();
+    assert_eq!(
+        (error.line, error.kind),
+        (5, RawVfErrorKind::InvalidTile('?'))
+    );
+
+    let error = parse_rawvf("Width: 2\nHeight: 1\nMines: 2\nBoard:\n*0\n").unwrap_err();
+    let kind = RawVfErrorKind::MineCount {
+        expected: 2,
+        found: 1,
+    };
+    assert_eq!((error.line, error.kind), (5, kind));
+
+    let text = "Width: 2\nHeight: 1\nMines: 1\nBoard:\n*0\nEvents:\n0.00 lr 3 1 (40 8)\n";
+    let error = parse_rawvf(text).unwrap_err();
+    let kind = RawVfErrorKind::OutOfBounds { column: 3, row: 1 };
+    assert_eq!((error.line, error.kind), (7, kind));
+
+    let error = parse_rawvf("Width: 2\nMines: 1\nBoard:\n*0\n").unwrap_err();
+    assert_eq!(error.kind, RawVfErrorKind::MissingHeader("Height"));
+}
@@ -0,0 +1,44 @@
+use board_plugin::formats::parse_layout;
+use board_plugin::resources::BoardOptions;
+use board_plugin::simulation::{RandomStrategy, SimpleSolver, Simulation};
+
+#[test]
+fn computes_three_bv() {
+    // One empty region and two isolated numbers
+    let tile_map = parse_layout("*...\n....\n..*.\n*...\n").unwrap();
+    assert_eq!(tile_map.three_bv(), 8);
+    let tile_map = parse_layout("....\n....\n....\n").unwrap();
+    assert_eq!(tile_map.three_bv(), 1);
+}
+
+#[test]
+fn seeded_simulations_are_reproducible() {
+    let options = BoardOptions {
+        map_size: (9, 9),
+        bomb_count: 10,
+        safe_start: true,
+        seed: Some(7),
+        ..Default::default()
+    };
+    let simulation = Simulation::new(options, 50);
+    let first = simulation.run(&mut RandomStrategy::default()).unwrap();
+    let second = simulation.run(&mut RandomStrategy::default()).unwrap();
+    assert_eq!((first.wins, first.losses), (second.wins, second.losses));
+    assert_eq!(first.three_bv.average, second.three_bv.average);
+    assert_eq!(first.games, 50);
+}
+
+#[test]
+fn solver_clears_trivial_boards() {
+    let options = BoardOptions {
+        source: board_plugin::resources::BoardSource::Layout("*..\n...\n...\n".into()),
+        safe_start: true,
+        ..Default::default()
+    };
+    let report = Simulation::new(options, 3)
+        .run(&mut SimpleSolver::default())
+        .unwrap();
+    assert_eq!(report.wins, 3);
+    assert_eq!(report.win_rate, 1.);
+    assert!(report.to_json().contains("\"win_rate\": 1.0"));
+}
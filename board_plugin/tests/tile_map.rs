@@ -0,0 +1,117 @@
+use board_plugin::components::Coordinates;
+use board_plugin::formats::parse_layout;
+use board_plugin::resources::{BoardRng, Tile, TileMap};
+use rand::SeedableRng;
+
+fn at(x: u16, y: u16) -> Coordinates {
+    Coordinates { x, y }
+}
+
+#[test]
+fn indexes_tiles_by_coordinates() {
+    let tile_map = parse_layout("*..\n...\n..o\n").unwrap();
+    assert_eq!(tile_map[at(0, 2)], Tile::Bomb(1));
+    assert_eq!(tile_map[at(1, 1)], Tile::BombNeighbor(1));
+    assert_eq!(tile_map[at(2, 0)], Tile::Empty);
+    let rows = tile_map.rows();
+    assert_eq!(rows.len(), 3);
+    assert!(rows.iter().all(|row| row.len() == 3));
+    assert_eq!(rows[2][0], tile_map[at(0, 2)]);
+}
+
+#[test]
+#[should_panic(expected = "out of the 3x3 map")]
+fn panics_on_out_of_range_indexing() {
+    let tile_map = TileMap::empty(3, 3);
+    let _ = tile_map[at(3, 0)];
+}
+
+#[test]
+fn keeps_the_layer_counters() {
+    let mut tile_map = TileMap::empty(4, 4).with_max_mines_per_tile(3);
+    tile_map.set_mines_at(at(0, 0), 2);
+    tile_map.set_mines_at(at(1, 0), 1);
+    tile_map.set_mines_at(at(1, 0), 3);
+    assert_eq!(tile_map.mined_count(), 2);
+    tile_map.set_mines_at(at(1, 0), 0);
+    assert_eq!(tile_map.mined_count(), 1);
+    tile_map.refresh_bomb_counts();
+
+    assert!(tile_map.reveal(at(3, 3)));
+    assert!(!tile_map.reveal(at(3, 3)));
+    assert_eq!(tile_map.revealed_count(), 1);
+
+    assert!(tile_map.set_flags_at(at(2, 2), 2));
+    assert_eq!(
+        (tile_map.flags_at(at(2, 2)), tile_map.flagged_count()),
+        (2, 2)
+    );
+    assert_eq!(tile_map.cycle_flag(at(0, 1)), Some(1));
+    assert_eq!(tile_map.flagged_count(), 3);
+    // Revealed tiles can't be flagged
+    assert_eq!(tile_map.cycle_flag(at(3, 3)), None);
+    assert!(!tile_map.set_flags_at(at(3, 3), 1));
+    assert!(tile_map.set_flags_at(at(2, 2), 0));
+    assert_eq!(tile_map.flagged_count(), 1);
+}
+
+#[test]
+fn tracks_revealed_mines() {
+    let mut tile_map = parse_layout("*.\n..\n").unwrap();
+    assert!(tile_map.reveal(at(1, 0)));
+    assert!(!tile_map.is_exploded());
+    // Mines placed under revealed tiles explode the map too
+    tile_map.set_mines_at(at(1, 0), 1);
+    assert!(tile_map.is_exploded());
+    tile_map.set_mines_at(at(1, 0), 0);
+    assert!(!tile_map.is_exploded());
+    assert_eq!(tile_map.reveal_from(at(0, 1)), vec![at(0, 1)]);
+    assert!(tile_map.is_exploded());
+}
+
+#[test]
+fn clamps_bombs_to_the_map_capacity() {
+    let mut rng = BoardRng::seed_from_u64(3);
+    let mut tile_map = TileMap::empty(3, 2).with_max_mines_per_tile(2);
+    tile_map.set_bombs_with_rng(20, &mut rng);
+    assert_eq!((tile_map.bomb_count(), tile_map.mined_count()), (12, 6));
+    assert!(tile_map.iter().all(|(_, tile)| tile == Tile::Bomb(2)));
+}
+
+#[test]
+fn counts_mined_tiles_apart_from_mines() {
+    let mut rng = BoardRng::seed_from_u64(5);
+    let mut tile_map = TileMap::empty(10, 10).with_max_mines_per_tile(3);
+    tile_map.set_bombs_with_rng(60, &mut rng);
+    let mined = tile_map.iter().filter(|(_, tile)| tile.is_bomb()).count();
+    let mines: u16 = tile_map
+        .iter()
+        .map(|(c, _)| tile_map.mines_at(c) as u16)
+        .sum();
+    assert_eq!(tile_map.bomb_count(), 60);
+    assert_eq!((tile_map.mined_count(), mines), (mined, 60));
+    // Stacked mines make fewer mined tiles than mines
+    assert!(tile_map.mined_count() < 60);
+}
+
+#[test]
+fn cycles_stacked_flags_and_question_marks() {
+    let mut tile_map = TileMap::empty(2, 2)
+        .with_max_mines_per_tile(3)
+        .with_question_marks(true);
+    let tile = at(1, 1);
+    for round in 0..2 {
+        for flags in 1..=3 {
+            assert_eq!(tile_map.cycle_flag(tile), Some(flags), "round {}", round);
+            assert_eq!(tile_map.flags_at(tile), flags);
+            assert_eq!(tile_map.flagged_count(), flags as usize);
+            assert!(!tile_map.is_questioned_at(tile));
+        }
+        // The flags are replaced by a question mark, then cleared
+        assert_eq!(tile_map.cycle_flag(tile), Some(0));
+        assert!(tile_map.is_questioned_at(tile));
+        assert_eq!((tile_map.flags_at(tile), tile_map.flagged_count()), (0, 0));
+        assert_eq!(tile_map.cycle_flag(tile), Some(0));
+        assert!(!tile_map.is_questioned_at(tile) && !tile_map.is_flagged_at(tile));
+    }
+}
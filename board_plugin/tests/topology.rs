@@ -0,0 +1,39 @@
+use board_plugin::components::Coordinates;
+use board_plugin::resources::{TileMap, Topology};
+
+fn at(x: u16, y: u16) -> Coordinates {
+    Coordinates { x, y }
+}
+
+#[test]
+fn picks_hexagonal_tiles_at_their_centers() {
+    let topology = Topology::Hexagonal;
+    for size in [10., 32.] {
+        for y in 0..4 {
+            for x in 0..5 {
+                let center = topology.tile_center(at(x, y), size);
+                assert_eq!(topology.tile_at(center, size), Some(at(x, y)));
+            }
+        }
+    }
+}
+
+#[test]
+fn finds_hexagonal_neighbors_on_even_and_odd_rows() {
+    let tile_map = TileMap::empty(5, 4).with_topology(Topology::Hexagonal);
+    let neighbors = |coordinates| {
+        let mut neighbors: Vec<_> = tile_map.safe_square_at(coordinates).collect();
+        neighbors.sort_by_key(|c| (c.y, c.x));
+        neighbors
+    };
+    // Even rows reach the previous column above and below
+    assert_eq!(
+        neighbors(at(2, 2)),
+        [at(1, 1), at(2, 1), at(1, 2), at(3, 2), at(1, 3), at(2, 3)]
+    );
+    // Odd rows are shifted right, reaching the next column instead
+    assert_eq!(
+        neighbors(at(2, 1)),
+        [at(2, 0), at(3, 0), at(1, 1), at(3, 1), at(2, 2), at(3, 2)]
+    );
+}
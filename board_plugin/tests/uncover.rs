@@ -0,0 +1,95 @@
+use bevy::ecs::event::ManualEventReader;
+use bevy::prelude::*;
+use board_plugin::components::Coordinates;
+use board_plugin::events::{TileTriggerEvent, TilesRevealedEvent};
+use board_plugin::resources::{
+    Board, BoardAssets, BoardOptions, BoardSource, SpriteMaterial, TileSize,
+};
+use board_plugin::BoardPlugin;
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+enum GameState {
+    #[default]
+    InGame,
+}
+
+fn board_app(options: BoardOptions) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Image>()
+        .add_asset::<Font>()
+        .add_asset::<TextureAtlas>()
+        .add_plugin(bevy::input::InputPlugin)
+        .add_plugin(HierarchyPlugin)
+        .add_plugin(TransformPlugin)
+        .add_state::<GameState>()
+        .add_plugin(BoardPlugin {
+            running_state: GameState::InGame,
+        })
+        .insert_resource(options)
+        .insert_resource(BoardAssets {
+            label: "Test".to_string(),
+            board_material: SpriteMaterial::default(),
+            tile_material: SpriteMaterial::default(),
+            covered_tile_material: SpriteMaterial::default(),
+            bomb_counter_font: Handle::default(),
+            bomb_counter_colors: BoardAssets::default_colors(),
+            high_contrast: false,
+            flag_material: SpriteMaterial::default(),
+            bomb_material: SpriteMaterial::default(),
+            skin: None,
+            sounds: Default::default(),
+        });
+    for _ in 0..3 {
+        app.update();
+    }
+    app
+}
+
+/// Reveal counts sent after revealing `coordinates` on the board of `app`
+fn reveal(app: &mut App, coordinates: Coordinates) -> Vec<usize> {
+    let board = app
+        .world
+        .query_filtered::<Entity, With<Board>>()
+        .single(&app.world);
+    app.world
+        .send_event(TileTriggerEvent { board, coordinates });
+    let mut reader = ManualEventReader::<TilesRevealedEvent>::default();
+    // Skips the events of the previous frames
+    reader.iter(app.world.resource::<Events<TilesRevealedEvent>>());
+    app.update();
+    reader
+        .iter(app.world.resource::<Events<TilesRevealedEvent>>())
+        .map(|e| e.count)
+        .collect()
+}
+
+#[test]
+fn counts_revealed_tiles_once_on_wrapping_boards() {
+    let mut app = board_app(BoardOptions {
+        source: BoardSource::Layout("...*\n....\n....\n....\n".into()),
+        wrapping: true,
+        tile_size: TileSize::Fixed(10.),
+        ..Default::default()
+    });
+    let board = app.world.query::<&Board>().single(&app.world);
+    let corner = Coordinates { x: 0, y: 0 };
+    // The corner is drawn again along both edges and in the opposite corner
+    assert_eq!(board.ghost_covers[&corner].len(), 3);
+    // Next to the mine across the corner, the tile reveals alone
+    assert_eq!(reveal(&mut app, corner), vec![1]);
+}
+
+#[test]
+fn ignores_reveals_on_completed_boards() {
+    let mut app = board_app(BoardOptions {
+        source: BoardSource::Layout("*o\noo\n".into()),
+        tile_size: TileSize::Fixed(10.),
+        ..Default::default()
+    });
+    let mine = Coordinates { x: 0, y: 1 };
+    assert!(reveal(&mut app, mine).is_empty());
+    let board = app.world.query::<&Board>().single(&app.world);
+    assert!(board.is_completed() && !board.is_exploded());
+}
@@ -0,0 +1,153 @@
+//! Two bots playing the same seeded board side by side, each board being an independent entity.
+//!
+//! `cargo run --example solver_duel`
+use bevy::prelude::*;
+use bevy::window::WindowResolution;
+
+use board_plugin::events::{
+    BoardCompletedEvent, BombExplosionEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent,
+};
+use board_plugin::resources::{
+    Action, Board, BoardAssets, BoardOptions, BoardPosition, SpriteMaterial, TileSize,
+};
+use board_plugin::simulation::{BoardView, RandomStrategy, SimpleSolver, Strategy};
+use board_plugin::BoardPlugin;
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+enum AppState {
+    #[default]
+    Out,
+    InGame,
+}
+
+/// Strategy playing a board
+#[derive(Component)]
+struct Bot(Box<dyn Strategy + Send + Sync>);
+
+/// Delay between two bot actions
+#[derive(Resource)]
+struct BotTimer(Timer);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                resolution: WindowResolution::new(1000.0, 500.0),
+                title: "Solver duel".to_string(),
+                ..default()
+            }),
+            ..default()
+        }))
+        .add_state::<AppState>()
+        .add_plugin(BoardPlugin {
+            running_state: AppState::InGame,
+        })
+        .insert_resource(BotTimer(Timer::from_seconds(0.15, TimerMode::Repeating)))
+        .add_startup_system(setup)
+        .add_systems((play_bots, stop_bots))
+        .run();
+}
+
+fn assets(asset_server: &AssetServer, covered: Color) -> BoardAssets {
+    BoardAssets {
+        label: "Duel".to_string(),
+        board_material: SpriteMaterial {
+            color: Color::WHITE,
+            ..Default::default()
+        },
+        tile_material: SpriteMaterial {
+            color: Color::DARK_GRAY,
+            ..Default::default()
+        },
+        covered_tile_material: SpriteMaterial {
+            color: covered,
+            ..Default::default()
+        },
+        bomb_counter_font: asset_server.load("fonts/pixeled.ttf"),
+        bomb_counter_colors: BoardAssets::default_colors(),
+        high_contrast: false,
+        flag_material: SpriteMaterial {
+            texture: asset_server.load("sprites/flag.png"),
+            color: Color::WHITE,
+        },
+        bomb_material: SpriteMaterial {
+            texture: asset_server.load("sprites/bomb.png"),
+            color: Color::WHITE,
+        },
+        skin: None,
+        sounds: Default::default(),
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut state: ResMut<NextState<AppState>>,
+) {
+    commands.spawn(Camera2dBundle::default());
+    let options = BoardOptions {
+        map_size: (16, 16),
+        bomb_count: 40,
+        seed: Some(2023),
+        safe_start: true,
+        tile_size: TileSize::Fixed(26.),
+        tile_padding: 1.,
+        ..Default::default()
+    };
+    let bots: [(f32, Color, Box<dyn Strategy + Send + Sync>); 2] = [
+        (-250., Color::GRAY, Box::<SimpleSolver>::default()),
+        (250., Color::SALMON, Box::<RandomStrategy>::default()),
+    ];
+    for (x, covered, strategy) in bots {
+        commands.spawn((
+            BoardOptions {
+                position: BoardPosition::Centered {
+                    offset: Vec3::new(x, 0., 0.),
+                },
+                ..options.clone()
+            },
+            assets(&asset_server, covered),
+            Bot(strategy),
+        ));
+    }
+    state.set(AppState::InGame);
+}
+
+fn play_bots(
+    time: Res<Time>,
+    mut timer: ResMut<BotTimer>,
+    mut bots: Query<(Entity, &Board, &mut Bot)>,
+    mut trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut mark_ewr: EventWriter<TileMarkEvent>,
+    mut chord_ewr: EventWriter<TileChordEvent>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    for (board, state, mut bot) in bots.iter_mut() {
+        match bot.0.next_action(&BoardView::new(&state.tile_map)) {
+            Some(Action::Reveal(coordinates)) => {
+                trigger_ewr.send(TileTriggerEvent { board, coordinates })
+            }
+            Some(Action::Mark(coordinates)) => mark_ewr.send(TileMarkEvent { board, coordinates }),
+            Some(Action::Chord(coordinates)) => {
+                chord_ewr.send(TileChordEvent { board, coordinates })
+            }
+            None => (),
+        }
+    }
+}
+
+/// Bots stop playing once their board is completed or exploded
+fn stop_bots(
+    mut commands: Commands,
+    mut completed_evr: EventReader<BoardCompletedEvent>,
+    mut explosion_evr: EventReader<BombExplosionEvent>,
+) {
+    let completed = completed_evr.iter().map(|e| (e.board, "cleared"));
+    let exploded = explosion_evr.iter().map(|e| (e.board, "exploded"));
+    for (board, outcome) in completed.chain(exploded) {
+        info!("Board {:?} {}", board, outcome);
+        commands.entity(board).remove::<Bot>();
+    }
+}
@@ -0,0 +1,277 @@
+//! Command line flags of the game, overriding the settings of the player for the session
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+
+use bevy::asset::FileAssetIo;
+use bevy::prelude::{AssetPlugin, Resource};
+use board_plugin::formats::{parse_layout, parse_rawvf};
+use board_plugin::resources::{Difficulty, Replay, TileMap};
+
+use crate::THEMES;
+
+/// Board size and mines without any flag
+const DEFAULT_MAP_SIZE: (u16, u16) = (20, 20);
+const DEFAULT_MINES: u16 = 40;
+
+pub const USAGE: &str = "\
+Usage: minesweeper-tutorial [OPTIONS]
+
+Options:
+  --width <N>           Board width in tiles
+  --height <N>          Board height in tiles
+  --mines <N>           Number of mines
+  --seed <N>            Seed of the first board, the same boards being generated for a seed
+  --preset <NAME>       Board preset: beginner, intermediate or expert
+  --theme <THEME>       Theme name (default, night, classic) or .theme.ron/.theme.toml file
+                        relative to the asset folder
+  --window <WxH>        Window size in pixels, like 700x800
+  --fullscreen          Starts in borderless fullscreen
+  --load <FILE>         Plays a saved text layout, or watches a .rawvf replay
+  --headless            Plays the boards with the solver without a window, printing a JSON
+                        report, or prints the outcome of the loaded replay
+  --games <N>           Number of boards played headless, 1 by default
+  -h, --help            Prints this help";
+
+/// Failed parsing of the command line
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CliError {
+    /// The help was requested
+    Help,
+    /// Invalid or inconsistent flags
+    Usage(String),
+    /// A file given on the command line can't be read
+    Load(String),
+}
+
+impl CliError {
+    /// Exit code of the process
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Help => 0,
+            Self::Load(_) => 1,
+            Self::Usage(_) => 2,
+        }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Help => write!(f, "{}", USAGE),
+            Self::Usage(message) => write!(f, "{}\n\n{}", message, USAGE),
+            Self::Load(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Game loaded with `--load`
+#[derive(Debug, Clone)]
+pub enum SavedGame {
+    /// Board layout, played from its saved state
+    Layout(TileMap),
+    /// Board watched while its actions are played back
+    Replay(Replay),
+}
+
+/// Parsed command line flags
+#[derive(Debug, Clone, Resource)]
+pub struct Cli {
+    pub map_size: (u16, u16),
+    pub mines: u16,
+    pub seed: Option<u64>,
+    /// Theme file, relative to the asset folder
+    pub theme: Option<String>,
+    /// Window size in pixels
+    pub window: Option<(f32, f32)>,
+    pub fullscreen: bool,
+    pub load: Option<SavedGame>,
+    pub headless: bool,
+    /// Boards played headless
+    pub games: usize,
+}
+
+impl Cli {
+    /// Parses the arguments of the process, without the program name.
+    ///
+    /// Values are given as `--flag value` or `--flag=value`, `--width`, `--height` and `--mines`
+    /// overriding the `--preset` ones.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut args = args.into_iter();
+        let (mut width, mut height, mut mines) = (None, None, None);
+        let mut preset = None;
+        let mut cli = Self {
+            map_size: DEFAULT_MAP_SIZE,
+            mines: DEFAULT_MINES,
+            seed: None,
+            theme: None,
+            window: None,
+            fullscreen: false,
+            load: None,
+            headless: false,
+            games: 1,
+        };
+        let mut load = None;
+        let mut games = None;
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| usage(format!("Missing value of `{}`", flag)))
+            };
+            match flag {
+                "-h" | "--help" => return Err(CliError::Help),
+                "--width" => width = Some(parse_size(flag, &value()?)?),
+                "--height" => height = Some(parse_size(flag, &value()?)?),
+                "--mines" => mines = Some(parse_number(flag, &value()?)?),
+                "--seed" => cli.seed = Some(parse_number(flag, &value()?)?),
+                "--preset" => preset = Some(parse_preset(&value()?)?),
+                "--theme" => cli.theme = Some(parse_theme(&value()?)?),
+                "--window" => cli.window = Some(parse_window(&value()?)?),
+                "--load" => load = Some(value()?),
+                "--games" => games = Some(parse_number(flag, &value()?)?),
+                "--fullscreen" | "--headless" if inline.is_some() => {
+                    return Err(usage(format!("`{}` takes no value", flag)));
+                }
+                "--fullscreen" => cli.fullscreen = true,
+                "--headless" => cli.headless = true,
+                _ => return Err(usage(format!("Unknown argument `{}`", arg))),
+            }
+        }
+
+        if let Some(preset) = preset {
+            cli.map_size = preset.map_size();
+            cli.mines = preset.bomb_count();
+        }
+        cli.map_size = (
+            width.unwrap_or(cli.map_size.0),
+            height.unwrap_or(cli.map_size.1),
+        );
+        cli.mines = mines.unwrap_or(cli.mines);
+        let tiles = cli.map_size.0 as u32 * cli.map_size.1 as u32;
+        if cli.mines as u32 >= tiles {
+            return Err(usage(format!(
+                "{} mines leave no safe tile on a {}x{} board",
+                cli.mines, cli.map_size.0, cli.map_size.1
+            )));
+        }
+
+        if let Some(path) = load {
+            let board_flags = width.is_some()
+                || height.is_some()
+                || mines.is_some()
+                || preset.is_some()
+                || cli.seed.is_some();
+            if board_flags {
+                return Err(usage(
+                    "`--load` can't be combined with the board flags".to_string(),
+                ));
+            }
+            cli.load = Some(load_game(Path::new(&path))?);
+        }
+
+        if cli.headless {
+            if cli.window.is_some() || cli.fullscreen || cli.theme.is_some() {
+                return Err(usage(
+                    "`--window`, `--fullscreen` and `--theme` need a window".to_string(),
+                ));
+            }
+        } else if games.is_some() {
+            return Err(usage("`--games` is only used by `--headless`".to_string()));
+        }
+        match games {
+            Some(0) => return Err(usage("`--games` must be at least 1".to_string())),
+            Some(games) => cli.games = games,
+            None => (),
+        }
+        Ok(cli)
+    }
+}
+
+fn usage(message: String) -> CliError {
+    CliError::Usage(message)
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, CliError> {
+    value
+        .parse()
+        .map_err(|_| usage(format!("Invalid `{}` value `{}`", flag, value)))
+}
+
+fn parse_size(flag: &str, value: &str) -> Result<u16, CliError> {
+    match parse_number(flag, value)? {
+        0 => Err(usage(format!("`{}` must be at least 1", flag))),
+        size => Ok(size),
+    }
+}
+
+fn parse_preset(value: &str) -> Result<Difficulty, CliError> {
+    Difficulty::ALL
+        .into_iter()
+        .find(|d| format!("{:?}", d).eq_ignore_ascii_case(value))
+        .ok_or_else(|| {
+            usage(format!(
+                "Unknown preset `{}`, expected beginner, intermediate or expert",
+                value
+            ))
+        })
+}
+
+/// Theme file of a theme name, or theme file of the asset folder
+fn parse_theme(value: &str) -> Result<String, CliError> {
+    if value.ends_with(".theme.ron") || value.ends_with(".theme.toml") {
+        // Looked up like the asset server does
+        let path = FileAssetIo::get_base_path()
+            .join(AssetPlugin::default().asset_folder)
+            .join(value);
+        if !path.is_file() {
+            let message = format!("Theme file {} not found", path.display());
+            return Err(CliError::Load(message));
+        }
+        return Ok(value.to_string());
+    }
+    THEMES
+        .into_iter()
+        .find(|path| {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            name.split('.').next() == Some(value)
+        })
+        .map(str::to_string)
+        .ok_or_else(|| {
+            usage(format!(
+                "Unknown theme `{}`, expected default, night, classic or a theme file",
+                value
+            ))
+        })
+}
+
+fn parse_window(value: &str) -> Result<(f32, f32), CliError> {
+    let invalid = || {
+        usage(format!(
+            "Invalid window size `{}`, expected like 700x800",
+            value
+        ))
+    };
+    let (width, height) = value.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let size = |s: &str| s.parse::<u32>().ok().filter(|s| *s > 0).ok_or_else(invalid);
+    Ok((size(width)? as f32, size(height)? as f32))
+}
+
+/// Reads a `.rawvf` replay, or a text layout
+fn load_game(path: &Path) -> Result<SavedGame, CliError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| CliError::Load(format!("Failed to read {}, {}", path.display(), e)))?;
+    let invalid = |e: &dyn Display| CliError::Load(format!("Invalid {}, {}", path.display(), e));
+    match path.extension() {
+        Some(extension) if extension == "rawvf" => parse_rawvf(&text)
+            .map(SavedGame::Replay)
+            .map_err(|e| invalid(&e)),
+        _ => parse_layout(&text)
+            .map(SavedGame::Layout)
+            .map_err(|e| invalid(&e)),
+    }
+}
@@ -0,0 +1,133 @@
+//! Daily challenge attempts, recorded in the player stats
+use bevy::{log, prelude::*};
+use board_plugin::daily::{AttemptKind, AttemptResult, DailyChallenge, DailyStats};
+use board_plugin::events::{BoardCompletedEvent, BombExplosionEvent};
+use board_plugin::resources::{Board, Difficulty, Localization};
+use board_plugin::simulation::Outcome;
+
+use crate::hud::{clear_race_hud, RaceHud};
+use crate::{data_dir, AppState, GameMode};
+
+/// Records the attempts of the daily challenges
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            start_daily_attempt
+                .after(clear_race_hud)
+                .in_schedule(OnEnter(AppState::InGame)),
+        )
+        .add_system(finish_daily_attempt.in_set(OnUpdate(AppState::InGame)))
+        .add_startup_system(load_stats);
+    }
+}
+
+/// Local daily challenge records, with the file they are saved to
+#[derive(Debug, Resource)]
+struct Stats {
+    path: std::path::PathBuf,
+    daily: DailyStats,
+}
+
+/// Daily challenge attempt being played
+#[derive(Debug, Resource)]
+pub struct DailyAttempt {
+    pub challenge: DailyChallenge,
+    pub kind: AttemptKind,
+    /// Time the attempt started at, in seconds
+    started: f32,
+}
+
+/// Cycles through the daily challenge difficulties, back to solo after the hardest one
+pub fn next_daily_challenge(mode: GameMode) -> GameMode {
+    let difficulty = match mode {
+        GameMode::Daily(challenge) => match challenge.difficulty {
+            Difficulty::Beginner => Difficulty::Intermediate,
+            Difficulty::Intermediate => Difficulty::Expert,
+            Difficulty::Expert => return GameMode::Solo,
+        },
+        _ => Difficulty::Beginner,
+    };
+    GameMode::Daily(DailyChallenge::today(difficulty))
+}
+
+/// Loads the daily challenge records from the user directory
+fn load_stats(mut commands: Commands) {
+    let path = data_dir().join("stats.json");
+    let daily = DailyStats::load(&path).unwrap_or_else(|e| {
+        log::error!("Failed to load the stats at {}, {}", path.display(), e);
+        DailyStats::default()
+    });
+    commands.insert_resource(Stats { path, daily });
+}
+
+/// Starts an attempt of the daily challenge, the first one of the day being official
+fn start_daily_attempt(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    time: Res<Time>,
+    stats: Option<ResMut<Stats>>,
+) {
+    commands.remove_resource::<DailyAttempt>();
+    let (GameMode::Daily(challenge), Some(mut stats)) = (*mode, stats) else {
+        return;
+    };
+    let kind = stats.daily.start_attempt(&challenge);
+    if let Err(e) = stats.daily.save(&stats.path) {
+        log::error!("Failed to save the stats, {}", e);
+    }
+    log::info!("starting {:?} attempt of {:?}", kind, challenge);
+    commands.insert_resource(DailyAttempt {
+        challenge,
+        kind,
+        started: time.elapsed_seconds(),
+    });
+}
+
+/// Records the result of the daily challenge attempt once the board is completed or exploded
+fn finish_daily_attempt(
+    mut commands: Commands,
+    time: Res<Time>,
+    attempt: Option<Res<DailyAttempt>>,
+    stats: Option<ResMut<Stats>>,
+    (boards, localization): (Query<&Board>, Res<Localization>),
+    (mut completed_evr, mut explosion_evr): (
+        EventReader<BoardCompletedEvent>,
+        EventReader<BombExplosionEvent>,
+    ),
+    mut hud: Query<&mut Text, With<RaceHud>>,
+) {
+    let won = completed_evr.iter().map(|e| (e.board, Outcome::Won));
+    let lost = explosion_evr.iter().map(|e| (e.board, Outcome::Lost));
+    let Some((board, outcome)) = won.chain(lost).last() else {
+        return;
+    };
+    let (Some(attempt), Some(mut stats)) = (attempt, stats) else {
+        return;
+    };
+    let result = AttemptResult {
+        outcome,
+        time: time.elapsed_seconds() - attempt.started,
+        three_bv: boards.get(board).map_or(0, |b| b.tile_map.three_bv()),
+    };
+    stats
+        .daily
+        .finish_attempt(&attempt.challenge, attempt.kind, result);
+    if let Err(e) = stats.daily.save(&stats.path) {
+        log::error!("Failed to save the stats, {}", e);
+    }
+    let announcement = match outcome {
+        Outcome::Won => localization.format(
+            "daily-cleared",
+            [("time", format!("{:.1}", result.time).into())],
+        ),
+        _ => localization.message("daily-exploded"),
+    };
+    log::info!("{:?} daily attempt: {}", attempt.kind, announcement);
+    for mut text in hud.iter_mut() {
+        text.sections[1].value.push('\n');
+        text.sections[1].value.push_str(&announcement);
+    }
+    commands.remove_resource::<DailyAttempt>();
+}
@@ -0,0 +1,329 @@
+//! Mode titles and announcements, versus progress bars and board announcements
+use bevy::{log, prelude::*};
+use board_plugin::components::{BoardAnnouncer, BoardPlayer, RemoteBoard};
+use board_plugin::daily::AttemptKind;
+use board_plugin::events::{BoardCompletedEvent, BombExplosionEvent};
+use board_plugin::net::{NetMode, PlayerProgress, PlayerState, DEFAULT_ADDRESS};
+use board_plugin::resources::{Difficulty, Localization};
+
+use crate::daily::DailyAttempt;
+use crate::{AppState, GameMode};
+
+/// Heads-up display of the game modes
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RaceWinner>()
+            .add_system(announcement_hud)
+            .add_system(race_hud.in_set(OnUpdate(AppState::InGame)))
+            .add_system(versus_hud)
+            .add_system(clear_race_hud.in_schedule(OnEnter(AppState::InGame)))
+            .add_system(race_hud_title)
+            .add_startup_system(hud_setup);
+    }
+}
+
+/// Race announcements text
+#[derive(Debug, Component)]
+pub struct RaceHud;
+
+/// Board of the first player to finish the race, cleared with the race HUD on every new game
+#[derive(Debug, Default, Resource)]
+pub struct RaceWinner(Option<Entity>);
+
+/// Versus progress bars and ranking
+#[derive(Debug, Component)]
+struct VersusHud;
+
+/// Last board announcement, like the requested neighborhood descriptions
+#[derive(Debug, Component)]
+struct AnnouncementHud;
+
+fn hud_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let style = TextStyle {
+        font: asset_server.load("fonts/pixeled.ttf"),
+        font_size: 18.,
+        color: Color::WHITE,
+    };
+    commands.spawn((
+        // Title of the mode, then its announcements
+        TextBundle::from_sections([
+            TextSection::new("", style.clone()),
+            TextSection::new("", style),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(10.),
+                left: Val::Px(10.),
+                ..default()
+            },
+            ..default()
+        }),
+        RaceHud,
+    ));
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(10.),
+                    right: Val::Px(10.),
+                    ..default()
+                },
+                flex_direction: FlexDirection::Column,
+                gap: Size::height(Val::Px(4.)),
+                ..default()
+            },
+            ..default()
+        },
+        VersusHud,
+    ));
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/pixeled.ttf"),
+                font_size: 14.,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(10.),
+                left: Val::Px(10.),
+                right: Val::Px(10.),
+                ..default()
+            },
+            ..default()
+        }),
+        AnnouncementHud,
+    ));
+}
+
+/// Shows the last board announcement
+fn announcement_hud(
+    announcers: Query<&BoardAnnouncer, Changed<BoardAnnouncer>>,
+    mut hud: Query<&mut Text, With<AnnouncementHud>>,
+) {
+    let Some(announcer) = announcers.iter().last() else {
+        return;
+    };
+    for mut text in hud.iter_mut() {
+        text.sections[0].value = announcer.text.clone();
+    }
+}
+
+pub fn clear_race_hud(mut hud: Query<&mut Text, With<RaceHud>>, mut winner: ResMut<RaceWinner>) {
+    winner.0 = None;
+    for mut text in hud.iter_mut() {
+        text.sections[1].value.clear();
+    }
+}
+
+/// Titles the HUD with the game mode, in the current language
+fn race_hud_title(
+    mode: Res<GameMode>,
+    localization: Res<Localization>,
+    attempt: Option<Res<DailyAttempt>>,
+    players: Query<Ref<BoardPlayer>>,
+    mut hud: Query<&mut Text, With<RaceHud>>,
+) {
+    let attempt_changed = attempt.as_ref().is_some_and(|a| a.is_changed());
+    let players_added = players.iter().any(|p| p.is_added());
+    if !mode.is_changed() && !localization.is_changed() && !attempt_changed && !players_added {
+        return;
+    }
+    let title = match *mode {
+        GameMode::Solo => String::new(),
+        GameMode::Online => {
+            localization.format("hud-online", [("address", DEFAULT_ADDRESS.into())])
+        }
+        GameMode::Spectator => localization.message("hud-spectator"),
+        GameMode::Race => {
+            let names: Vec<String> = players.iter().map(|p| p.name.clone()).collect();
+            let versus = format!(" {} ", localization.message("hud-race-versus"));
+            localization.format("hud-race", [("players", names.join(&versus).into())])
+        }
+        GameMode::Daily(challenge) => {
+            let difficulty = match challenge.difficulty {
+                Difficulty::Beginner => "difficulty-beginner",
+                Difficulty::Intermediate => "difficulty-intermediate",
+                Difficulty::Expert => "difficulty-expert",
+            };
+            let title = localization.format(
+                "hud-daily",
+                [
+                    ("date", challenge.date.to_string().into()),
+                    ("difficulty", localization.message(difficulty).into()),
+                ],
+            );
+            let kind = attempt
+                .filter(|a| a.challenge == challenge)
+                .map(|a| match a.kind {
+                    AttemptKind::Official => "hud-daily-official",
+                    AttemptKind::Practice => "hud-daily-practice",
+                });
+            match kind {
+                Some(kind) => localization.format(kind, [("challenge", title.into())]),
+                None => title,
+            }
+        }
+    };
+    for mut text in hud.iter_mut() {
+        text.sections[0].value = title.clone();
+    }
+}
+
+/// Announces who finished first and who exploded
+fn race_hud(
+    mode: Res<GameMode>,
+    (players, localization): (Query<&BoardPlayer>, Res<Localization>),
+    mut completed_evr: EventReader<BoardCompletedEvent>,
+    mut explosion_evr: EventReader<BombExplosionEvent>,
+    mut hud: Query<&mut Text, With<RaceHud>>,
+    mut winner: ResMut<RaceWinner>,
+) {
+    if *mode != GameMode::Race {
+        return;
+    }
+    let announce = |id, board| {
+        let name = match players.get(board) {
+            Ok(player) => player.name.clone(),
+            Err(_) => localization.message("player-someone"),
+        };
+        localization.format(id, [("player", name.into())])
+    };
+    let mut announcements = Vec::new();
+    for event in completed_evr.iter() {
+        if winner.0.is_none() {
+            winner.0 = Some(event.board);
+            announcements.push(announce("race-finished-first", event.board));
+        } else {
+            announcements.push(announce("race-finished", event.board));
+        }
+    }
+    for event in explosion_evr.iter() {
+        announcements.push(announce("race-exploded", event.board));
+    }
+    for mut text in hud.iter_mut() {
+        for announcement in &announcements {
+            log::info!("{}", announcement);
+            text.sections[1].value.push('\n');
+            text.sections[1].value.push_str(announcement);
+        }
+    }
+}
+
+/// Rebuilds the versus progress bars of the players, and the final ranking
+fn versus_hud(
+    mut commands: Commands,
+    (asset_server, localization): (Res<AssetServer>, Res<Localization>),
+    boards: Query<Ref<RemoteBoard>>,
+    hud: Query<Entity, With<VersusHud>>,
+    mut shown: Local<bool>,
+) {
+    let board = boards.iter().find(|b| b.mode == NetMode::Versus);
+    let changed = |b: &Ref<RemoteBoard>| b.is_changed() || localization.is_changed();
+    if !board.as_ref().map_or(*shown, changed) {
+        return;
+    }
+    *shown = board.is_some();
+    let style = TextStyle {
+        font: asset_server.load("fonts/pixeled.ttf"),
+        font_size: 12.,
+        color: Color::WHITE,
+    };
+    for entity in hud.iter() {
+        commands.entity(entity).despawn_descendants();
+        let Some(board) = &board else {
+            continue;
+        };
+        let mut players: Vec<&PlayerProgress> = board.progress.values().collect();
+        players.sort_by_key(|p| p.player);
+        commands.entity(entity).with_children(|parent| {
+            for progress in players {
+                let status = match progress.state {
+                    PlayerState::Playing => "versus-alive",
+                    PlayerState::Won => "versus-cleared",
+                    PlayerState::Lost => "versus-dead",
+                };
+                parent.spawn(TextBundle::from_section(
+                    localization.format(
+                        "versus-status",
+                        [
+                            ("player", progress.name.as_str().into()),
+                            ("cleared", format!("{:.0}", progress.cleared * 100.).into()),
+                            ("flags", progress.flags.into()),
+                            ("state", localization.message(status).into()),
+                        ],
+                    ),
+                    style.clone(),
+                ));
+                spawn_progress_bar(parent, progress);
+            }
+            if let Some(ranking) = &board.ranking {
+                parent.spawn(TextBundle::from_section(
+                    localization.message("versus-ranking"),
+                    style.clone(),
+                ));
+                for (rank, progress) in ranking.iter().enumerate() {
+                    let result = match (progress.time, progress.three_bv_per_second) {
+                        (Some(time), Some(speed)) => localization.format(
+                            "versus-time",
+                            [
+                                ("time", format!("{:.2}", time).into()),
+                                ("speed", format!("{:.2}", speed).into()),
+                            ],
+                        ),
+                        _ => localization.format(
+                            "versus-dead-at",
+                            [("cleared", format!("{:.0}", progress.cleared * 100.).into())],
+                        ),
+                    };
+                    parent.spawn(TextBundle::from_section(
+                        localization.format(
+                            "versus-rank",
+                            [
+                                ("rank", (rank + 1).into()),
+                                ("player", progress.name.as_str().into()),
+                                ("result", result.into()),
+                            ],
+                        ),
+                        style.clone(),
+                    ));
+                }
+            }
+        });
+    }
+}
+
+/// Bar filled with the cleared fraction of a player board, colored by the player state
+fn spawn_progress_bar(parent: &mut ChildBuilder, progress: &PlayerProgress) {
+    let color = match progress.state {
+        PlayerState::Playing => Color::GOLD,
+        PlayerState::Won => Color::LIME_GREEN,
+        PlayerState::Lost => Color::CRIMSON,
+    };
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(200.), Val::Px(8.)),
+                ..default()
+            },
+            background_color: Color::DARK_GRAY.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(progress.cleared * 100.), Val::Percent(100.)),
+                    ..default()
+                },
+                background_color: color.into(),
+                ..default()
+            });
+        });
+}
@@ -1,13 +1,39 @@
+use bevy::core_pipeline::clear_color::ClearColorConfig;
 use bevy::log::LogPlugin;
-use bevy::window::WindowResolution;
+use bevy::render::view::RenderLayers;
+use bevy::window::{WindowMode, WindowResolution};
 use bevy::{log, prelude::*};
 
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 
-use board_plugin::resources::{BoardAssets, BoardOptions, SpriteMaterial};
+use board_plugin::components::{
+    BoardCamera, BoardInput, BoardPlayer, GamepadBindings, KeyBindings,
+};
+use board_plugin::daily::DailyChallenge;
+use board_plugin::formats::write_layout;
+use board_plugin::net::{
+    ClientMessage, NetClient, SpectatorClient, DEFAULT_ADDRESS, DEFAULT_SPECTATOR_ADDRESS,
+};
+use board_plugin::resources::{BoardAssets, BoardOptions, BoardPalette, BoardSource, Localization};
+use board_plugin::settings::{Settings, DEFAULT_THEME};
+use board_plugin::simulation::{SimpleSolver, Simulation};
 use board_plugin::BoardPlugin;
 
+use cli::{Cli, CliError, SavedGame};
+use daily::{next_daily_challenge, DailyPlugin};
+use hud::HudPlugin;
+use replay::{replay_report, ReplayPlayback, ReplayPlugin};
+use settings_menu::{settings_closed, SettingsMenuPlugin, UserSettings};
+use spectator::SpectatorPlugin;
+
+mod cli;
+mod daily;
+mod hud;
+mod replay;
+mod settings_menu;
+mod spectator;
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum AppState {
     #[default]
@@ -15,7 +41,62 @@ pub enum AppState {
     InGame,
 }
 
+/// Theme files, cycled through from the settings
+const THEMES: [&str; 3] = [
+    DEFAULT_THEME,
+    "themes/night.theme.toml",
+    "themes/classic.theme.ron",
+];
+
+/// Number of players sharing the window
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Resource)]
+pub enum GameMode {
+    #[default]
+    Solo,
+    /// Two players racing on the same layout in split screen
+    Race,
+    /// Playing on a local game server with other players, in co-op or versus
+    Online,
+    /// Watching the game streamed by another instance
+    Spectator,
+    /// Board of the day, the same for every player
+    Daily(DailyChallenge),
+}
+
+/// Board options of the solo mode, the other modes deriving their options from them
+#[derive(Debug, Clone, Resource)]
+struct BaseBoardOptions(BoardOptions);
+
+/// Requests a new game once the current one is cleared
+#[derive(Debug, Resource)]
+struct Restart;
+
+/// Main camera, overlaying the HUD in race mode
+#[derive(Debug, Component)]
+struct MainCamera;
+
 fn main() {
+    let cli = match Cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(CliError::Help) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    };
+    if cli.headless {
+        run_headless(&cli);
+        return;
+    }
+
+    let (width, height) = cli.window.unwrap_or((700.0, 800.0));
+    let mode = match cli.fullscreen {
+        true => WindowMode::BorderlessFullscreen,
+        false => WindowMode::Windowed,
+    };
     let mut app = App::new();
 
     // Add default plugins
@@ -23,8 +104,10 @@ fn main() {
         DefaultPlugins
             .set(WindowPlugin {
                 primary_window: Some(Window {
-                    resolution: WindowResolution::new(700.0, 800.0),
-                    title: "Mine Sweeper!".to_string(),
+                    resolution: WindowResolution::new(width, height),
+                    mode,
+                    // Set from the catalogs once loaded
+                    title: String::new(),
                     ..default()
                 }),
                 ..default()
@@ -32,6 +115,11 @@ fn main() {
             .set(LogPlugin {
                 level: log::Level::DEBUG,
                 ..default()
+            })
+            // Reloads the edited theme files
+            .set(AssetPlugin {
+                watch_for_changes: true,
+                ..default()
             }),
     );
 
@@ -40,65 +128,235 @@ fn main() {
     app.add_plugin(WorldInspectorPlugin::new());
 
     app.add_state::<AppState>()
+        .init_resource::<GameMode>()
+        .insert_resource(cli)
         .add_plugin(BoardPlugin {
             running_state: AppState::InGame,
         })
-        .add_system(state_handler)
+        .add_plugin(SettingsMenuPlugin)
+        .add_plugin(HudPlugin)
+        .add_plugin(DailyPlugin)
+        .add_plugin(ReplayPlugin)
+        .add_plugin(SpectatorPlugin)
+        .add_system(start_game.run_if(resource_exists::<BoardAssets>()))
+        .add_systems((state_handler, cursor_handler).distributive_run_if(settings_closed))
+        .add_system(restart_game)
+        .add_system(setup_cameras)
         .add_startup_system(setup_board)
         .add_startup_system(camera_setup)
         .run();
 }
 
-fn setup_board(
-    mut commands: Commands,
-    mut state: ResMut<NextState<AppState>>,
-    asset_server: Res<AssetServer>,
-) {
-    // Board plugin options
-    commands.insert_resource(BoardOptions {
-        map_size: (20, 20),
-        bomb_count: 40,
-        tile_padding: 1.0,
+/// Directory of the settings and records of the player
+fn data_dir() -> std::path::PathBuf {
+    let home = std::env::var_os("HOME").map_or_else(Default::default, std::path::PathBuf::from);
+    home.join(".minesweeper-tutorial")
+}
+
+/// Plays the boards with the solver and prints a JSON report, or the outcome of the loaded replay
+fn run_headless(cli: &Cli) {
+    let source = match &cli.load {
+        Some(SavedGame::Replay(replay)) => {
+            println!("{}", replay_report(replay));
+            return;
+        }
+        Some(SavedGame::Layout(tile_map)) => BoardSource::Layout(write_layout(tile_map)),
+        None => BoardSource::Random,
+    };
+    let options = BoardOptions {
+        map_size: cli.map_size,
+        bomb_count: cli.mines,
+        seed: cli.seed,
         safe_start: true,
+        source,
         ..Default::default()
+    };
+    match Simulation::new(options, cli.games).run(&mut SimpleSolver::default()) {
+        Ok(report) => println!("{}", report.to_json()),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn setup_board(mut commands: Commands, asset_server: Res<AssetServer>, cli: Res<Cli>) {
+    let path = data_dir().join("settings.ron");
+    let settings = Settings::load(&path).unwrap_or_else(|e| {
+        log::error!("Failed to load the settings at {}, {}", path.display(), e);
+        Settings::default()
     });
 
-    // Board assets
-    commands.insert_resource(BoardAssets {
-        label: "Default".to_string(),
-        board_material: SpriteMaterial {
-            color: Color::WHITE,
-            ..Default::default()
-        },
-        tile_material: SpriteMaterial {
-            color: Color::DARK_GRAY,
-            ..Default::default()
-        },
-        covered_tile_material: SpriteMaterial {
-            color: Color::GRAY,
-            ..Default::default()
-        },
-        bomb_counter_font: asset_server.load("fonts/pixeled.ttf"),
-        bomb_counter_colors: BoardAssets::default_colors(),
-        flag_material: SpriteMaterial {
-            texture: asset_server.load("sprites/flag.png"),
-            color: Color::WHITE,
-        },
-        bomb_material: SpriteMaterial {
-            texture: asset_server.load("sprites/bomb.png"),
-            color: Color::WHITE,
+    // Board plugin options
+    let options = settings.board_options(BoardOptions {
+        map_size: cli.map_size,
+        bomb_count: cli.mines,
+        seed: cli.seed,
+        ..Default::default()
+    });
+    commands.insert_resource(BaseBoardOptions(options.clone()));
+    // The loaded game is only played until the mode changes
+    let layout = match &cli.load {
+        Some(SavedGame::Layout(tile_map)) => Some(write_layout(tile_map)),
+        Some(SavedGame::Replay(replay)) => {
+            let layout = write_layout(&replay.tile_map);
+            commands.insert_resource(ReplayPlayback::new(layout.clone(), replay.actions.clone()));
+            Some(layout)
+        }
+        None => None,
+    };
+    commands.insert_resource(match layout {
+        Some(layout) => BoardOptions {
+            source: BoardSource::Layout(layout),
+            safe_start: false,
+            ..options
         },
+        None => options,
     });
 
-    // Plugin activation
-    state.set(AppState::InGame);
+    // Board assets and tile padding, the theme flag lasting until the theme setting changes
+    let mut theme = settings.board_theme(&asset_server);
+    if let Some(path) = &cli.theme {
+        theme.theme = asset_server.load(path.as_str());
+    }
+    commands.insert_resource(theme);
+    commands.init_resource::<BoardPalette>();
+    commands.insert_resource(settings.audio.clone());
+    commands.insert_resource(settings.animations.clone());
+    commands.insert_resource(UserSettings { path, settings });
+}
+
+/// Plugin activation, once the theme is loaded
+fn start_game(mut state: ResMut<NextState<AppState>>, mut started: Local<bool>) {
+    if !std::mem::replace(&mut *started, true) {
+        state.set(AppState::InGame);
+    }
 }
 
 fn camera_setup(mut commands: Commands) {
-    commands.spawn(Camera2dBundle::default());
+    commands.spawn((Camera2dBundle::default(), MainCamera));
 }
 
-fn state_handler(mut next_state: ResMut<NextState<AppState>>, keys: Res<Input<KeyCode>>) {
+/// Switches the solo player between the mouse and a keyboard cursor, restarting the game
+fn cursor_handler(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    mode: Res<GameMode>,
+    mut next_state: ResMut<NextState<AppState>>,
+    (options, base): (
+        Option<ResMut<BoardOptions>>,
+        Option<ResMut<BaseBoardOptions>>,
+    ),
+    user: Res<UserSettings>,
+) {
+    if !keys.just_pressed(KeyCode::K) {
+        return;
+    }
+    let (Some(mut options), Some(mut base)) = (options, base) else {
+        return;
+    };
+    let keyboard = !base
+        .0
+        .players
+        .iter()
+        .any(|p| matches!(p.input, BoardInput::Keyboard(_)));
+    let input = match keyboard {
+        true => BoardInput::Keyboard(user.settings.key_bindings.clone()),
+        false => BoardInput::Mouse,
+    };
+    log::info!("keyboard cursor: {}", keyboard);
+    base.0.players = vec![BoardPlayer { input, ..default() }];
+    // Race and networked boards keep their players until back to solo
+    if matches!(*mode, GameMode::Solo | GameMode::Daily(_)) {
+        options.players = base.0.players.clone();
+        commands.insert_resource(Restart);
+        next_state.set(AppState::Out);
+    }
+}
+
+/// Race players: mouse against keyboard, or two gamepads when connected
+fn race_players(
+    gamepads: &Gamepads,
+    localization: &Localization,
+    keys: &KeyBindings,
+) -> Vec<BoardPlayer> {
+    let mut gamepads = gamepads.iter();
+    let inputs = match (gamepads.next(), gamepads.next()) {
+        (Some(first), Some(second)) => [
+            BoardInput::Gamepad(GamepadBindings::new(first)),
+            BoardInput::Gamepad(GamepadBindings::new(second)),
+        ],
+        _ => [BoardInput::Mouse, BoardInput::Keyboard(keys.clone())],
+    };
+    inputs
+        .into_iter()
+        .enumerate()
+        .map(|(i, input)| BoardPlayer {
+            name: localization.format("player-name", [("number", (i + 1).into())]),
+            input,
+        })
+        .collect()
+}
+
+/// Starts a new game after a restart request cleared the previous one
+fn restart_game(
+    mut commands: Commands,
+    restart: Option<Res<Restart>>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if restart.is_some() && state.0 == AppState::Out {
+        commands.remove_resource::<Restart>();
+        next_state.set(AppState::InGame);
+    }
+}
+
+/// Lets the split screen board cameras render the boards under the main camera HUD
+fn setup_cameras(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    mut main_camera: Query<(Entity, &mut Camera2d), With<MainCamera>>,
+    board_cameras: Query<Entity, Added<BoardCamera>>,
+) {
+    for entity in board_cameras.iter() {
+        commands
+            .entity(entity)
+            .insert(UiCameraConfig { show_ui: false });
+    }
+    if !mode.is_changed() {
+        return;
+    }
+    for (entity, mut camera) in main_camera.iter_mut() {
+        match *mode {
+            GameMode::Solo | GameMode::Online | GameMode::Spectator | GameMode::Daily(_) => {
+                camera.clear_color = ClearColorConfig::Default;
+                commands.entity(entity).remove::<RenderLayers>();
+            }
+            GameMode::Race => {
+                camera.clear_color = ClearColorConfig::None;
+                commands.entity(entity).insert(RenderLayers::none());
+            }
+        }
+    }
+}
+
+fn state_handler(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+    keys: Res<Input<KeyCode>>,
+    mut mode: ResMut<GameMode>,
+    (options, base): (Option<ResMut<BoardOptions>>, Option<Res<BaseBoardOptions>>),
+    (gamepads, localization, user): (Res<Gamepads>, Res<Localization>, Res<UserSettings>),
+    client: Option<Res<NetClient>>,
+) {
+    if let (Some(client), true) = (&client, keys.just_pressed(KeyCode::C)) {
+        // The server starts a new board once the current one is over
+        log::info!("requesting a new co-op board");
+        if let Err(e) = client.send(&ClientMessage::Restart) {
+            log::error!("Failed to request a new board, {}", e);
+        }
+        return;
+    }
     if keys.just_pressed(KeyCode::C) {
         log::debug!("clearing detected");
         log::info!("clearing game");
@@ -109,4 +367,73 @@ fn state_handler(mut next_state: ResMut<NextState<AppState>>, keys: Res<Input<Ke
         log::info!("loading game");
         next_state.set(AppState::InGame);
     }
+    let requested = if keys.just_pressed(KeyCode::R) {
+        GameMode::Race
+    } else if keys.just_pressed(KeyCode::N) {
+        GameMode::Online
+    } else if keys.just_pressed(KeyCode::V) {
+        GameMode::Spectator
+    } else if keys.just_pressed(KeyCode::D) {
+        next_daily_challenge(*mode)
+    } else {
+        return;
+    };
+    let (Some(mut options), Some(base)) = (options, base) else {
+        return;
+    };
+    // Requesting the current mode goes back to solo
+    let requested = if *mode == requested {
+        GameMode::Solo
+    } else {
+        requested
+    };
+    let connected = match requested {
+        GameMode::Online => {
+            let name =
+                std::env::var("USER").unwrap_or_else(|_| localization.message("player-default"));
+            NetClient::connect(DEFAULT_ADDRESS, name).map(|c| commands.insert_resource(c))
+        }
+        GameMode::Spectator => {
+            SpectatorClient::connect(DEFAULT_SPECTATOR_ADDRESS).map(|c| commands.insert_resource(c))
+        }
+        GameMode::Solo | GameMode::Race | GameMode::Daily(_) => Ok(()),
+    };
+    if let Err(e) = connected {
+        log::error!("Failed to start {:?} mode, {}", requested, e);
+        return;
+    }
+    if requested != GameMode::Online {
+        commands.remove_resource::<NetClient>();
+    }
+    if requested != GameMode::Spectator {
+        commands.remove_resource::<SpectatorClient>();
+    }
+    *mode = requested;
+    log::info!("restarting game in {:?} mode", *mode);
+    let players = race_players(&gamepads, &localization, &user.settings.key_bindings);
+    *options = mode_options(*mode, &options, base.0.clone(), players);
+    commands.insert_resource(Restart);
+    next_state.set(AppState::Out);
+}
+
+/// Board options of a game mode, derived from the solo ones and keeping the tile padding of the
+/// theme from `current`
+fn mode_options(
+    mode: GameMode,
+    current: &BoardOptions,
+    base: BoardOptions,
+    race_players: Vec<BoardPlayer>,
+) -> BoardOptions {
+    let options = match mode {
+        GameMode::Race => BoardOptions {
+            players: race_players,
+            ..base
+        },
+        GameMode::Daily(challenge) => challenge.board_options(base),
+        _ => base,
+    };
+    BoardOptions {
+        tile_padding: current.tile_padding,
+        ..options
+    }
 }
@@ -0,0 +1,106 @@
+//! Playback of the replay loaded from the command line
+use bevy::{log, prelude::*};
+use board_plugin::events::{TileChordEvent, TileMarkEvent, TileTriggerEvent};
+use board_plugin::resources::{Action, Board, BoardOptions, BoardSource, Replay, TimedAction};
+use board_plugin::simulation::Outcome;
+
+use crate::AppState;
+
+/// Plays the loaded replay on its board
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            play_replay
+                .run_if(resource_exists::<ReplayPlayback>())
+                .in_set(OnUpdate(AppState::InGame)),
+        );
+    }
+}
+
+/// Replay loaded from the command line, its actions being played on the board of its layout
+#[derive(Debug, Resource)]
+pub struct ReplayPlayback {
+    /// Layout of the replayed board
+    layout: String,
+    actions: Vec<TimedAction>,
+    /// Board the actions are played on, the replay starting over on a new board
+    board: Option<Entity>,
+    /// Time the replay started at, in seconds
+    started: f32,
+    /// Index of the next action to play
+    next: usize,
+}
+
+impl ReplayPlayback {
+    /// Playback of `actions` on the boards of `layout`
+    pub fn new(layout: String, actions: Vec<TimedAction>) -> Self {
+        Self {
+            layout,
+            actions,
+            board: None,
+            started: 0.0,
+            next: 0,
+        }
+    }
+}
+
+/// JSON outcome of a replay
+pub fn replay_report(replay: &Replay) -> String {
+    let tile_map = replay.play();
+    let outcome = if tile_map.is_exploded() {
+        Outcome::Lost
+    } else if tile_map.is_completed() {
+        Outcome::Won
+    } else {
+        Outcome::Abandoned
+    };
+    let time = replay.actions.last().map_or(0.0, |a| a.time);
+    format!(
+        r#"{{"outcome":"{:?}","actions":{},"time":{},"three_bv":{}}}"#,
+        outcome,
+        replay.actions.len(),
+        time,
+        replay.tile_map.three_bv()
+    )
+}
+
+/// Plays the actions of the loaded replay at their time, starting over on every new board of
+/// the replayed layout
+fn play_replay(
+    time: Res<Time>,
+    options: Res<BoardOptions>,
+    mut playback: ResMut<ReplayPlayback>,
+    boards: Query<Entity, With<Board>>,
+    (mut trigger_ewr, mut mark_ewr, mut chord_ewr): (
+        EventWriter<TileTriggerEvent>,
+        EventWriter<TileMarkEvent>,
+        EventWriter<TileChordEvent>,
+    ),
+) {
+    let replayed = matches!(&options.source, BoardSource::Layout(l) if *l == playback.layout);
+    let (true, Some(board)) = (replayed, boards.iter().next()) else {
+        return;
+    };
+    let now = time.elapsed_seconds();
+    if playback.board != Some(board) {
+        log::info!("replaying {} actions", playback.actions.len());
+        playback.board = Some(board);
+        playback.started = now;
+        playback.next = 0;
+    }
+    while let Some(timed) = playback.actions.get(playback.next) {
+        if timed.time > now - playback.started {
+            break;
+        }
+        match timed.action {
+            Action::Reveal(coordinates) => {
+                trigger_ewr.send(TileTriggerEvent { board, coordinates })
+            }
+            Action::Mark(coordinates) => mark_ewr.send(TileMarkEvent { board, coordinates }),
+            Action::Chord(coordinates) => chord_ewr.send(TileChordEvent { board, coordinates }),
+        }
+        playback.next += 1;
+    }
+}
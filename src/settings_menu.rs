@@ -0,0 +1,613 @@
+//! Player settings: the settings screen, the hotkeys changing them and their file
+use bevy::app::AppExit;
+use bevy::window::PrimaryWindow;
+use bevy::{log, prelude::*};
+use board_plugin::components::KeyBindings;
+use board_plugin::events::SkipAnimationsEvent;
+use board_plugin::resources::{
+    AnimationSettings, AudioSettings, BoardOptions, BoardPalette, BoardTheme, Locale, Localization,
+    Palette, TileSize,
+};
+use board_plugin::settings::Settings;
+
+use crate::{mode_options, race_players, AppState, BaseBoardOptions, GameMode, Restart, THEMES};
+
+/// Settings screen and the settings hotkeys, applying and saving the settings
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        // The settings screen takes over the keyboard while open
+        app.add_systems(
+            (
+                theme_handler,
+                language_handler,
+                audio_handler,
+                animation_handler,
+                open_settings,
+            )
+                .distributive_run_if(settings_closed),
+        )
+        .add_system(settings_menu.run_if(resource_exists::<SettingsMenu>()))
+        .add_system(
+            apply_settings
+                .run_if(resource_changed::<UserSettings>())
+                .before(settings_menu),
+        )
+        .add_system(settings_hud)
+        .add_system(save_settings.in_base_set(CoreSet::Last))
+        .add_system(window_title.run_if(resource_changed::<Localization>()))
+        .add_startup_system(settings_hud_setup);
+    }
+}
+
+/// Languages having a catalog, the first one being used on startup
+const LANGUAGES: [&str; 3] = ["en-US", "fr", "ru"];
+
+/// Player settings, with the file they are saved to.
+///
+/// Changes are applied to the plugin resources at once, and to the boards of the next game
+#[derive(Debug, Resource)]
+pub struct UserSettings {
+    pub path: std::path::PathBuf,
+    pub settings: Settings,
+}
+
+/// Settings screen, open while the resource exists. The game is cleared meanwhile, and
+/// restarted with the new settings once closed
+#[derive(Debug, Default, Resource)]
+pub struct SettingsMenu {
+    /// Index of the highlighted row
+    selected: usize,
+    /// Waiting for the key to bind to the highlighted row
+    rebinding: bool,
+    /// Last key refused while rebinding
+    rejected: Option<KeyRejection>,
+}
+
+/// Key refused by a key binding row
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum KeyRejection {
+    /// Key of a game hotkey or of the settings screen
+    Reserved(KeyCode),
+    /// Key already bound to another row
+    Taken(KeyCode, SettingsRow),
+}
+
+/// Keys of the game hotkeys, which the keyboard cursors would trigger as well
+const HOTKEYS: [KeyCode; 19] = [
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::R,
+    KeyCode::N,
+    KeyCode::V,
+    KeyCode::G,
+    KeyCode::K,
+    KeyCode::T,
+    KeyCode::B,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::U,
+    KeyCode::L,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::M,
+    KeyCode::Minus,
+    KeyCode::Equals,
+    KeyCode::Escape,
+];
+
+/// Keys browsing the settings screen, only bound back to their default cursor action
+const MENU_KEYS: [KeyCode; 5] = [
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Return,
+];
+
+/// Rows of the settings screen
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum SettingsRow {
+    Theme,
+    TileSize,
+    Padding,
+    SafeStart,
+    QuestionMarks,
+    Sounds,
+    Volume,
+    Animations,
+    RevealAnimation,
+    KeyUp,
+    KeyDown,
+    KeyLeft,
+    KeyRight,
+    KeyReveal,
+    KeyMark,
+    KeyChord,
+    KeyDescribe,
+}
+
+/// Settings screen text
+#[derive(Debug, Component)]
+struct SettingsHud;
+
+/// Spawns the hidden settings screen
+fn settings_hud_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle {
+            visibility: Visibility::Hidden,
+            // Above the HUD of the other plugins
+            z_index: ZIndex::Global(1),
+            background_color: Color::rgba(0., 0., 0., 0.85).into(),
+            ..TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/pixeled.ttf"),
+                    font_size: 14.,
+                    color: Color::WHITE,
+                },
+            )
+        }
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(60.),
+                left: Val::Px(40.),
+                right: Val::Px(40.),
+                ..default()
+            },
+            padding: UiRect::all(Val::Px(16.)),
+            ..default()
+        }),
+        SettingsHud,
+    ));
+}
+
+/// Switches to the next theme or counter palette, and toggles the high contrast cues
+fn theme_handler(
+    keys: Res<Input<KeyCode>>,
+    mut user: ResMut<UserSettings>,
+    palette: Option<ResMut<BoardPalette>>,
+) {
+    if keys.just_pressed(KeyCode::T) {
+        next_theme(&mut user.settings, 1);
+        log::info!("loading theme {}", user.settings.theme);
+    }
+    let Some(mut palette) = palette else {
+        return;
+    };
+    if keys.just_pressed(KeyCode::B) {
+        let index = Palette::ALL.iter().position(|p| *p == palette.palette);
+        palette.palette = Palette::ALL[index.map_or(0, |i| i + 1) % Palette::ALL.len()];
+        log::info!("using the {:?} palette", palette.palette);
+    }
+    if keys.just_pressed(KeyCode::H) {
+        palette.high_contrast = !palette.high_contrast;
+        log::info!("high contrast: {}", palette.high_contrast);
+    }
+}
+
+/// Switches to the next language
+fn language_handler(
+    keys: Res<Input<KeyCode>>,
+    mut locale: ResMut<Locale>,
+    mut selected: Local<usize>,
+) {
+    if !keys.just_pressed(KeyCode::I) {
+        return;
+    }
+    *selected = (*selected + 1) % LANGUAGES.len();
+    match LANGUAGES[*selected].parse() {
+        Ok(language) => *locale = Locale::new(language),
+        Err(e) => log::error!("Invalid language {}, {}", LANGUAGES[*selected], e),
+    }
+}
+
+/// Translates the window title, once the catalogs are loaded
+fn window_title(
+    localization: Res<Localization>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !localization.is_loaded() {
+        return;
+    }
+    for mut window in windows.iter_mut() {
+        window.title = localization.message("window-title");
+    }
+}
+
+/// Mutes the sounds, and turns their volume down or up
+fn audio_handler(keys: Res<Input<KeyCode>>, mut user: ResMut<UserSettings>) {
+    if keys.just_pressed(KeyCode::U) {
+        let settings = &mut user.settings.audio;
+        settings.muted = !settings.muted;
+        log::info!("muted: {}", settings.muted);
+    }
+    let step = if keys.just_pressed(KeyCode::Minus) {
+        -0.1
+    } else if keys.just_pressed(KeyCode::Equals) {
+        0.1
+    } else {
+        return;
+    };
+    let settings = &mut user.settings.audio;
+    settings.master_volume = (settings.master_volume + step).clamp(0., 1.);
+    log::info!("volume: {:.1}", settings.master_volume);
+}
+
+/// Skips the running animations, and toggles reduced motion
+fn animation_handler(
+    keys: Res<Input<KeyCode>>,
+    mut user: ResMut<UserSettings>,
+    mut skip_ewr: EventWriter<SkipAnimationsEvent>,
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        skip_ewr.send(SkipAnimationsEvent);
+    }
+    if keys.just_pressed(KeyCode::L) {
+        let settings = &mut user.settings.animations;
+        settings.reduced_motion = !settings.reduced_motion;
+        log::info!("reduced motion: {}", settings.reduced_motion);
+    }
+}
+
+/// Applies the changed settings to the plugin resources, the board ones being used by the next
+/// game
+fn apply_settings(
+    mut commands: Commands,
+    user: Res<UserSettings>,
+    asset_server: Res<AssetServer>,
+    board_theme: Option<Res<BoardTheme>>,
+    mut base: ResMut<BaseBoardOptions>,
+    (mut audio, mut animations): (ResMut<AudioSettings>, ResMut<AnimationSettings>),
+) {
+    // Applied by the setup, along with the command line flags
+    if user.is_added() {
+        return;
+    }
+    let settings = &user.settings;
+    let theme = settings.board_theme(&asset_server);
+    // Reapplying the same theme would rebuild the boards
+    let unchanged =
+        board_theme.is_some_and(|t| t.theme == theme.theme && t.tile_padding == theme.tile_padding);
+    if !unchanged {
+        commands.insert_resource(theme);
+    }
+    *audio = settings.audio.clone();
+    *animations = settings.animations.clone();
+    base.0 = settings.board_options(base.0.clone());
+}
+
+/// Saves the settings when the game exits
+fn save_settings(user: Option<Res<UserSettings>>, mut exit_evr: EventReader<AppExit>) {
+    if exit_evr.iter().last().is_none() {
+        return;
+    }
+    if let Some(user) = user {
+        if let Err(e) = user.settings.save(&user.path) {
+            log::error!("Failed to save the settings, {}", e);
+        }
+    }
+}
+
+/// Run condition of the game key handlers, disabled by the settings screen
+pub fn settings_closed(menu: Option<Res<SettingsMenu>>) -> bool {
+    menu.is_none()
+}
+
+/// Opens the settings screen, clearing the game
+fn open_settings(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keys.just_pressed(KeyCode::O) {
+        log::info!("opening the settings");
+        commands.init_resource::<SettingsMenu>();
+        next_state.set(AppState::Out);
+    }
+}
+
+/// Browses and changes the settings, binds the keys of the keyboard cursors, and restarts the
+/// game with the new settings once closed
+fn settings_menu(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    (mut menu, mut user): (ResMut<SettingsMenu>, ResMut<UserSettings>),
+    (mode, gamepads, localization): (Res<GameMode>, Res<Gamepads>, Res<Localization>),
+    (mut options, base): (ResMut<BoardOptions>, Res<BaseBoardOptions>),
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let row = SettingsRow::ALL[menu.selected];
+    if menu.rebinding {
+        let Some(key) = keys.get_just_pressed().next().copied() else {
+            return;
+        };
+        if key != KeyCode::Escape {
+            // Refused keys keep waiting for another key
+            if let Some(rejection) = row.rejection(key, &user.settings.key_bindings) {
+                log::info!("{:?} can't be bound, {:?}", row, rejection);
+                menu.rejected = Some(rejection);
+                return;
+            }
+            if let Some(binding) = row.binding(&mut user.settings.key_bindings) {
+                *binding = key;
+            }
+        }
+        menu.rebinding = false;
+        menu.rejected = None;
+        return;
+    }
+    if keys.any_just_pressed([KeyCode::O, KeyCode::Escape]) {
+        log::info!("closing the settings");
+        if let Err(e) = user.settings.save(&user.path) {
+            log::error!("Failed to save the settings, {}", e);
+        }
+        let players = race_players(&gamepads, &localization, &user.settings.key_bindings);
+        *options = mode_options(*mode, &options, base.0.clone(), players);
+        commands.remove_resource::<SettingsMenu>();
+        commands.insert_resource(Restart);
+        next_state.set(AppState::Out);
+        return;
+    }
+    let rows = SettingsRow::ALL.len();
+    if keys.just_pressed(KeyCode::Up) {
+        menu.selected = (menu.selected + rows - 1) % rows;
+    }
+    if keys.just_pressed(KeyCode::Down) {
+        menu.selected = (menu.selected + 1) % rows;
+    }
+    if keys.just_pressed(KeyCode::Return) && row.is_binding() {
+        menu.rebinding = true;
+    }
+    let step = if keys.just_pressed(KeyCode::Left) {
+        -1
+    } else if keys.just_pressed(KeyCode::Right) {
+        1
+    } else {
+        return;
+    };
+    row.change(&mut user.settings, step);
+}
+
+/// Shows the settings screen while open
+fn settings_hud(
+    menu: Option<Res<SettingsMenu>>,
+    user: Res<UserSettings>,
+    localization: Res<Localization>,
+    mut hud: Query<(&mut Text, &mut Visibility), With<SettingsHud>>,
+) {
+    let Some(menu) = menu else {
+        for (_, mut visibility) in hud.iter_mut() {
+            if *visibility != Visibility::Hidden {
+                *visibility = Visibility::Hidden;
+            }
+        }
+        return;
+    };
+    if !menu.is_changed() && !user.is_changed() && !localization.is_changed() {
+        return;
+    }
+    let mut lines = vec![localization.message("settings-title"), String::new()];
+    for (index, row) in SettingsRow::ALL.iter().enumerate() {
+        let line = localization.format(
+            "settings-row",
+            [
+                ("label", localization.message(row.label()).into()),
+                ("value", row.value(&user.settings, &localization).into()),
+            ],
+        );
+        let cursor = if index == menu.selected { "> " } else { "  " };
+        lines.push(format!("{}{}", cursor, line));
+    }
+    lines.push(String::new());
+    lines.push(match (menu.rebinding, menu.rejected) {
+        (true, Some(rejection)) => rejection.message(&localization),
+        (true, None) => localization.message("settings-rebinding"),
+        (false, _) => localization.message("settings-help"),
+    });
+    for (mut text, mut visibility) in hud.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+        *visibility = Visibility::Inherited;
+    }
+}
+
+impl SettingsRow {
+    const ALL: [Self; 17] = [
+        Self::Theme,
+        Self::TileSize,
+        Self::Padding,
+        Self::SafeStart,
+        Self::QuestionMarks,
+        Self::Sounds,
+        Self::Volume,
+        Self::Animations,
+        Self::RevealAnimation,
+        Self::KeyUp,
+        Self::KeyDown,
+        Self::KeyLeft,
+        Self::KeyRight,
+        Self::KeyReveal,
+        Self::KeyMark,
+        Self::KeyChord,
+        Self::KeyDescribe,
+    ];
+
+    /// Tile sizes to choose from, the adaptive one first
+    const FIXED_TILE_SIZES: [f32; 5] = [20., 30., 40., 50., 60.];
+
+    /// Message of the row label
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Theme => "settings-theme",
+            Self::TileSize => "settings-tile-size",
+            Self::Padding => "settings-padding",
+            Self::SafeStart => "settings-safe-start",
+            Self::QuestionMarks => "settings-question-marks",
+            Self::Sounds => "settings-sounds",
+            Self::Volume => "settings-volume",
+            Self::Animations => "settings-animations",
+            Self::RevealAnimation => "settings-reveal-animation",
+            Self::KeyUp => "settings-key-up",
+            Self::KeyDown => "settings-key-down",
+            Self::KeyLeft => "settings-key-left",
+            Self::KeyRight => "settings-key-right",
+            Self::KeyReveal => "settings-key-reveal",
+            Self::KeyMark => "settings-key-mark",
+            Self::KeyChord => "settings-key-chord",
+            Self::KeyDescribe => "settings-key-describe",
+        }
+    }
+
+    /// Is the row a key binding row
+    fn is_binding(&self) -> bool {
+        matches!(
+            self,
+            Self::KeyUp
+                | Self::KeyDown
+                | Self::KeyLeft
+                | Self::KeyRight
+                | Self::KeyReveal
+                | Self::KeyMark
+                | Self::KeyChord
+                | Self::KeyDescribe
+        )
+    }
+
+    /// Key bound by the row in `bindings`, if it's a key binding row
+    fn bound_key(&self, bindings: &KeyBindings) -> Option<KeyCode> {
+        self.binding(&mut bindings.clone()).copied()
+    }
+
+    /// Why `key` can't be bound to the row, the hotkeys and the keys of the other rows being
+    /// refused
+    fn rejection(&self, key: KeyCode, bindings: &KeyBindings) -> Option<KeyRejection> {
+        let default = self.bound_key(&KeyBindings::arrows());
+        if HOTKEYS.contains(&key) || (MENU_KEYS.contains(&key) && default != Some(key)) {
+            return Some(KeyRejection::Reserved(key));
+        }
+        Self::ALL
+            .into_iter()
+            .find(|row| row != self && row.bound_key(bindings) == Some(key))
+            .map(|row| KeyRejection::Taken(key, row))
+    }
+
+    /// Mutable key bound by the row, if it's a key binding row
+    fn binding<'a>(&self, bindings: &'a mut KeyBindings) -> Option<&'a mut KeyCode> {
+        match self {
+            Self::KeyUp => Some(&mut bindings.up),
+            Self::KeyDown => Some(&mut bindings.down),
+            Self::KeyLeft => Some(&mut bindings.left),
+            Self::KeyRight => Some(&mut bindings.right),
+            Self::KeyReveal => Some(&mut bindings.reveal),
+            Self::KeyMark => Some(&mut bindings.mark),
+            Self::KeyChord => Some(&mut bindings.chord),
+            Self::KeyDescribe => Some(&mut bindings.describe),
+            _ => None,
+        }
+    }
+
+    /// Current value of the row setting, in the current language
+    fn value(&self, settings: &Settings, localization: &Localization) -> String {
+        let toggle = |on: bool| match on {
+            true => localization.message("settings-on"),
+            false => localization.message("settings-off"),
+        };
+        match self {
+            // Theme files are named after their theme
+            Self::Theme => settings
+                .theme
+                .rsplit('/')
+                .next()
+                .and_then(|name| name.split('.').next())
+                .unwrap_or_default()
+                .to_string(),
+            Self::TileSize => match settings.tile_size {
+                TileSize::Adaptive { .. } => localization.message("settings-tile-adaptive"),
+                TileSize::Fixed(size) => {
+                    localization.format("settings-tile-fixed", [("size", size.into())])
+                }
+            },
+            Self::Padding => match settings.tile_padding {
+                Some(padding) => padding.to_string(),
+                None => localization.message("settings-padding-theme"),
+            },
+            Self::SafeStart => toggle(settings.safe_start),
+            Self::QuestionMarks => toggle(settings.question_marks),
+            Self::Sounds => toggle(!settings.audio.muted),
+            Self::Volume => format!("{:.0}%", settings.audio.master_volume * 100.),
+            Self::Animations => toggle(!settings.animations.reduced_motion),
+            Self::RevealAnimation => toggle(settings.reveal_animation),
+            _ => self
+                .bound_key(&settings.key_bindings)
+                .map_or_else(String::new, |key| format!("{:?}", key)),
+        }
+    }
+
+    /// Switches the row setting to its `step`th next or previous value
+    fn change(&self, settings: &mut Settings, step: i32) {
+        match self {
+            Self::Theme => next_theme(settings, step),
+            Self::TileSize => {
+                let sizes: Vec<TileSize> = std::iter::once(TileSize::default())
+                    .chain(Self::FIXED_TILE_SIZES.map(TileSize::Fixed))
+                    .collect();
+                let index = sizes.iter().position(|s| *s == settings.tile_size);
+                settings.tile_size = sizes[cycle(index, step, sizes.len())].clone();
+            }
+            Self::Padding => {
+                // The theme padding comes before no padding
+                let padding = settings.tile_padding.map_or(-1., |p| p) + step as f32;
+                settings.tile_padding = (padding >= 0.).then_some(padding.min(5.));
+            }
+            Self::SafeStart => settings.safe_start = !settings.safe_start,
+            Self::QuestionMarks => settings.question_marks = !settings.question_marks,
+            Self::Sounds => settings.audio.muted = !settings.audio.muted,
+            Self::Volume => {
+                let volume = settings.audio.master_volume + step as f32 * 0.1;
+                settings.audio.master_volume = volume.clamp(0., 1.);
+            }
+            Self::Animations => {
+                settings.animations.reduced_motion = !settings.animations.reduced_motion;
+            }
+            Self::RevealAnimation => settings.reveal_animation = !settings.reveal_animation,
+            // Keys are bound by pressing them
+            _ => (),
+        }
+    }
+}
+
+impl KeyRejection {
+    /// Explanation of the rejection, in the current language
+    fn message(&self, localization: &Localization) -> String {
+        match self {
+            Self::Reserved(key) => localization.format(
+                "settings-key-reserved",
+                [("key", format!("{:?}", key).into())],
+            ),
+            Self::Taken(key, row) => localization.format(
+                "settings-key-taken",
+                [
+                    ("key", format!("{:?}", key).into()),
+                    ("action", localization.message(row.label()).into()),
+                ],
+            ),
+        }
+    }
+}
+
+/// Switches to the `step`th next or previous theme
+fn next_theme(settings: &mut Settings, step: i32) {
+    let index = THEMES.iter().position(|t| *t == settings.theme);
+    settings.theme = THEMES[cycle(index, step, THEMES.len())].to_string();
+}
+
+/// Index `step` places away from `index` in a cycle of `len` values, the first one if `index`
+/// is missing
+fn cycle(index: Option<usize>, step: i32, len: usize) -> usize {
+    match index {
+        Some(index) => (index as i32 + step).rem_euclid(len as i32) as usize,
+        None => 0,
+    }
+}
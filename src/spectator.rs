@@ -0,0 +1,43 @@
+//! Streaming of the game to spectators
+use bevy::{log, prelude::*};
+use board_plugin::components::SpectatedBoard;
+use board_plugin::net::{SpectatorServer, DEFAULT_SPECTATOR_ADDRESS};
+
+use crate::settings_menu::settings_closed;
+
+/// Toggles the spectator stream
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(spectator_handler.run_if(settings_closed));
+    }
+}
+
+/// Toggles streaming the game to spectators, and the mines shown to spectators
+fn spectator_handler(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    server: Option<Res<SpectatorServer>>,
+    mut spectated: Query<&mut SpectatedBoard>,
+) {
+    if keys.just_pressed(KeyCode::P) {
+        if server.is_some() {
+            log::info!("stopped streaming to spectators");
+            commands.remove_resource::<SpectatorServer>();
+        } else {
+            match SpectatorServer::bind(DEFAULT_SPECTATOR_ADDRESS) {
+                Ok(server) => {
+                    log::info!("streaming to spectators on {}", DEFAULT_SPECTATOR_ADDRESS);
+                    commands.insert_resource(server);
+                }
+                Err(e) => log::error!("Failed to stream to spectators, {}", e),
+            }
+        }
+    }
+    if keys.just_pressed(KeyCode::M) {
+        for mut board in spectated.iter_mut() {
+            board.show_mines = !board.show_mines;
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_minesweeper-tutorial"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Exit code and first stderr line of an invalid command line
+fn error(args: &[&str]) -> (Option<i32>, String) {
+    let output = run(args);
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let line = stderr.lines().next().unwrap_or_default().to_string();
+    (output.status.code(), line)
+}
+
+#[test]
+fn prints_the_help() {
+    let output = run(&["--help"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("Usage: minesweeper-tutorial"));
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn rejects_invalid_flags() {
+    let cases = [
+        (&["--bogus"][..], "error: Unknown argument `--bogus`"),
+        (&["--width"], "error: Missing value of `--width`"),
+        (&["--width=0"], "error: `--width` must be at least 1"),
+        (
+            &["--preset", "beginner", "--mines", "81"],
+            "error: 81 mines leave no safe tile on a 9x9 board",
+        ),
+        (
+            &["--load", "board.txt", "--width", "9"],
+            "error: `--load` can't be combined with the board flags",
+        ),
+        (
+            &["--games", "3"],
+            "error: `--games` is only used by `--headless`",
+        ),
+        (
+            &["--headless", "--window", "800x600"],
+            "error: `--window`, `--fullscreen` and `--theme` need a window",
+        ),
+        (
+            &["--fullscreen=yes"],
+            "error: `--fullscreen` takes no value",
+        ),
+    ];
+    for (args, message) in cases {
+        assert_eq!(error(args), (Some(2), message.to_string()), "{:?}", args);
+    }
+}
+
+#[test]
+fn rejects_missing_files() {
+    let (code, message) = error(&["--load", "missing/board.txt"]);
+    assert_eq!(code, Some(1));
+    assert!(message.starts_with("error: Failed to read missing/board.txt"));
+    let (code, message) = error(&["--theme", "themes/missing.theme.ron"]);
+    assert_eq!(code, Some(1));
+    assert!(message.ends_with("missing.theme.ron not found"));
+}
+
+#[test]
+fn plays_headless_boards() {
+    let output = run(&[
+        "--headless",
+        "--width=9",
+        "--height=9",
+        "--mines=10",
+        "--seed=3",
+        "--games",
+        "2",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(report.contains("\"games\": 2"));
+}